@@ -0,0 +1,85 @@
+use crate::fuzz;
+use crate::gen::Rng;
+use crate::minimise::Predicate;
+use crate::solver::Solver;
+use crate::types::MyResult;
+use std::path::PathBuf;
+
+/// Seeds tried one after another before giving up on keeping the outcome.
+const ATTEMPTS: u64 = 20;
+
+/// Where the `anonymise` command writes its inputs.
+pub fn out_dir() -> PathBuf {
+    PathBuf::from("fuzz/anonymised")
+}
+
+/// `input` of `day` of `year` with its numbers and letters changed so that
+/// it can be shared, keeping the structure the solvers depend on. Lines that
+/// do not look like puzzle input are left as they are.
+pub fn anonymise(year: u32, day: u32, input: &str, seed: u64) -> MyResult<String> {
+    let anonymise = crate::year(year)?.anonymise;
+    let anonymised = anonymise(day, &mut Rng::new(seed), input);
+    Ok(anonymised.ok_or_else(|| format!("no anonymiser for {} day {}", year, day))?)
+}
+
+/// What running `input` comes to, with numbers left out of the messages.
+fn outcome(solver: &dyn Solver, input: &str) -> String {
+    match fuzz::execute(solver, input) {
+        Ok(outcome) => outcome,
+        Err(crash) => fuzz::kind(&crash),
+    }
+}
+
+/// An anonymised `input` on which the solver ends the same way, answering,
+/// failing or panicking, and on which `predicate` still holds if there is
+/// one. Seeds from `seed` on are tried until one keeps both; the seed used
+/// is returned with the input.
+pub fn preserving(
+    solver: &dyn Solver,
+    input: &str,
+    seed: u64,
+    predicate: Option<&Predicate>,
+) -> MyResult<(u64, String)> {
+    fuzz::quietly(|| {
+        if let Some(predicate) = predicate.filter(|predicate| !predicate.holds(solver, input)) {
+            return Err(format!("the input does not start out as one that {}", predicate).into());
+        }
+        let expected = outcome(solver, input);
+        for seed in seed..seed + ATTEMPTS {
+            let anonymised = anonymise(solver.year(), solver.day(), input, seed)?;
+            if outcome(solver, &anonymised) == expected
+                && predicate.is_none_or(|predicate| predicate.holds(solver, &anonymised))
+            {
+                return Ok((seed, anonymised));
+            }
+        }
+        Err(format!("no anonymised input in {} seeds ends in '{}' like the input", ATTEMPTS, expected).into())
+    })
+}
+
+#[test]
+fn test_anonymise_keeps_structure() {
+    use crate::inputs;
+    use crate::solver::Day;
+    use crate::y2023::day2::Day2;
+    use crate::y2023::day4::Day4;
+
+    for solver in crate::y2023::DAYS {
+        let day = solver.day();
+        let input = std::fs::read_to_string(inputs::dir(2023).join(format!("input{}.txt", day))).unwrap();
+        let (_, anonymised) = preserving(*solver, &input, 0, None).unwrap();
+        assert_ne!(anonymised, input, "day{}", day);
+        assert_eq!(anonymised.lines().count(), input.lines().count(), "day{}", day);
+        assert_eq!(outcome(*solver, &anonymised), "ok / ok", "day{}", day);
+    }
+
+    let input = std::fs::read_to_string("input/2023/input4.txt").unwrap();
+    let matches = |input: &str| Day4::parse(input).unwrap().iter().map(|card| card.matches()).collect::<Vec<_>>();
+    assert_eq!(matches(&anonymise(2023, 4, &input, 3).unwrap()), matches(&input));
+    assert_eq!(anonymise(2023, 1, "xtwone3Q\n", 0).unwrap()[1..7], *"twone3");
+
+    // The overflow of part two is what is being shared, so it has to stay.
+    let input = "Game 7: 4294967295 red, 4294967295 blue, 4294967295 green\n";
+    let (_, anonymised) = preserving(&Day2, input, 0, None).unwrap();
+    assert_eq!(outcome(&Day2, &anonymised), "ok / arithmetic overflow");
+}
@@ -0,0 +1,78 @@
+use crate::inputs;
+use crate::solver::{Answer, Part};
+use crate::types::MyResult;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Known answers of a day, one `pN: answer` line per part. A part with no
+/// value is not known yet.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    pub p1: Option<Answer>,
+    pub p2: Option<Answer>,
+}
+
+impl Answers {
+    pub fn path(year: u32, day: u32) -> PathBuf {
+        inputs::dir(year).join(format!("answers{}.txt", day))
+    }
+    /// Answers of `day`, empty if the day has no answers file.
+    pub fn load(year: u32, day: u32) -> MyResult<Answers> {
+        let path = Answers::path(year, day);
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        fs::read_to_string(&path)?
+            .parse()
+            .map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+    pub fn get(&self, part: Part) -> Option<Answer> {
+        match part {
+            Part::One => self.p1,
+            Part::Two => self.p2,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Box<dyn Error>;
+
+    fn from_str(text: &str) -> MyResult<Answers> {
+        let mut answers = Answers::default();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (part, value) = line.split_once(':').ok_or_else(|| format!("'{}'?", line))?;
+            let value = match value.trim() {
+                "" => None,
+                value => Some(value.parse()?),
+            };
+            match part.trim() {
+                "p1" => answers.p1 = value,
+                "p2" => answers.p2 = value,
+                part => return Err(format!("part '{}'?", part).into()),
+            }
+        }
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |answer: Option<Answer>| answer.map_or(String::new(), |a| format!(" {}", a));
+        writeln!(f, "p1:{}", show(self.p1))?;
+        writeln!(f, "p2:{}", show(self.p2))
+    }
+}
+
+#[test]
+fn test_answers_round_trip() {
+    let answers: Answers = "# day 4\np1: 13\np2:\n".parse().unwrap();
+    assert_eq!(answers, Answers { p1: Some(13), p2: None });
+    assert_eq!(answers.to_string(), "p1: 13\np2:\n");
+    assert!("p3: 1".parse::<Answers>().is_err());
+}
@@ -0,0 +1,262 @@
+use crate::inputs;
+use crate::json::Json;
+use crate::memory::{self, Usage};
+use crate::solver::Run;
+use crate::types::MyResult;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        Stats {
+            min: sorted.first().copied().unwrap_or_default(),
+            median: sorted.get(sorted.len() / 2).copied().unwrap_or_default(),
+            max: sorted.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// Timing of one step (`parse`, `p1` or `p2`) of a day, and its heap use
+/// when the counting allocator is installed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timing {
+    pub year: u32,
+    pub day: u32,
+    pub step: String,
+    pub stats: Stats,
+    pub memory: Option<Usage>,
+}
+
+/// Year, day and step, which name a step across runs.
+pub type Key = (u32, u32, String);
+
+impl Timing {
+    fn key(&self) -> Key {
+        (self.year, self.day, self.step.clone())
+    }
+}
+
+pub fn timings(run: &Run) -> Vec<Timing> {
+    let steps = [
+        ("parse", &run.parse, run.parse_memory),
+        ("p1", &run.parts[0], run.part_memory[0]),
+        ("p2", &run.parts[1], run.part_memory[1]),
+    ];
+    steps
+        .into_iter()
+        .map(|(step, samples, memory)| Timing {
+            year: run.year,
+            day: run.day,
+            step: step.into(),
+            stats: Stats::of(samples),
+            memory,
+        })
+        .collect()
+}
+
+fn timing_json(timing: &Timing) -> Json {
+    let nanos = |d: Duration| Json::from(d.as_nanos() as u64);
+    let mut fields = vec![
+        ("year".into(), Json::from(timing.year as u64)),
+        ("day".into(), Json::from(timing.day as u64)),
+        ("step".into(), Json::from(timing.step.as_str())),
+        ("min_ns".into(), nanos(timing.stats.min)),
+        ("median_ns".into(), nanos(timing.stats.median)),
+        ("max_ns".into(), nanos(timing.stats.max)),
+    ];
+    if let Some(memory) = timing.memory {
+        fields.push(("allocations".into(), Json::from(memory.allocations)));
+        fields.push(("allocated_bytes".into(), Json::from(memory.bytes)));
+        fields.push(("peak_bytes".into(), Json::from(memory.peak)));
+    }
+    Json::Object(fields)
+}
+
+fn timing_of(json: &Json) -> Option<Timing> {
+    let nanos = |key| json.get(key).and_then(Json::as_u64).map(Duration::from_nanos);
+    let number = |key| json.get(key).and_then(Json::as_u64);
+    Some(Timing {
+        // Histories from before there were years only had the default one.
+        year: number("year").map_or(inputs::YEAR, |year| year as u32),
+        day: json.get("day")?.as_u64()? as u32,
+        step: json.get("step")?.as_str()?.into(),
+        stats: Stats {
+            min: nanos("min_ns")?,
+            median: nanos("median_ns")?,
+            max: nanos("max_ns")?,
+        },
+        // Runs without the counting allocator have no memory fields.
+        memory: number("peak_bytes").map(|peak| Usage {
+            allocations: number("allocations").unwrap_or(0),
+            bytes: number("allocated_bytes").unwrap_or(0),
+            peak,
+        }),
+    })
+}
+
+/// Runs kept in the history file; older ones are dropped as new ones come.
+pub const HISTORY_RUNS: usize = 200;
+
+/// Past benchmark runs, oldest first, as stored in the history file.
+pub struct History {
+    runs: Vec<Json>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> MyResult<History> {
+        if !path.exists() {
+            return Ok(History { runs: vec![] });
+        }
+        let json = Json::parse(&fs::read_to_string(path)?)?;
+        let runs = json.get("runs").and_then(Json::as_array).ok_or("bench history: runs?")?;
+        Ok(History { runs: runs.to_vec() })
+    }
+
+    /// Latest timing of every step, keyed by year, day and step, so a run of
+    /// a single day does not hide the other days from the next comparison.
+    pub fn previous(&self) -> HashMap<Key, Timing> {
+        self.runs
+            .iter()
+            .filter_map(|run| run.get("timings").and_then(Json::as_array))
+            .flatten()
+            .filter_map(timing_of)
+            .map(|timing| (timing.key(), timing))
+            .collect()
+    }
+
+    pub fn push(&mut self, runs: usize, timings: &[Timing]) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.runs.push(Json::Object(vec![
+            ("timestamp".into(), Json::from(timestamp)),
+            ("runs".into(), Json::from(runs as u64)),
+            ("timings".into(), Json::Array(timings.iter().map(timing_json).collect())),
+        ]));
+        let over = self.runs.len().saturating_sub(HISTORY_RUNS);
+        self.runs.drain(..over);
+    }
+
+    pub fn save(&self, path: &Path) -> MyResult<()> {
+        let json = Json::Object(vec![("runs".into(), Json::Array(self.runs.clone()))]);
+        fs::write(path, json.to_string() + "\n")?;
+        Ok(())
+    }
+}
+
+/// Median change against `previous` in percent, if the step was timed before.
+fn change(timing: &Timing, previous: &HashMap<Key, Timing>) -> Option<f64> {
+    let prev = previous.get(&timing.key())?;
+    let prev = prev.stats.median.as_secs_f64();
+    (prev > 0.0).then(|| (timing.stats.median.as_secs_f64() / prev - 1.0) * 100.0)
+}
+
+/// Peak heap change against `previous` in percent, if both runs counted it.
+fn peak_change(timing: &Timing, previous: &HashMap<Key, Timing>) -> Option<f64> {
+    let prev = previous.get(&timing.key())?.memory?.peak;
+    let peak = timing.memory?.peak;
+    (prev > 0).then(|| (peak as f64 / prev as f64 - 1.0) * 100.0)
+}
+
+/// Formats a table of `timings` and lists the steps whose median or peak
+/// heap grew by more than `threshold` percent since `previous`.
+pub fn report(
+    timings: &[Timing],
+    previous: &HashMap<Key, Timing>,
+    threshold: f64,
+) -> (String, Vec<String>) {
+    let mut table = String::new();
+    let mut regressions = vec![];
+    writeln!(
+        table,
+        "{:>4} {:>3} {:<5} {:>10} {:>10} {:>10} {:>8} {:>8} {:>10} {:>8}",
+        "year", "day", "step", "min", "median", "max", "change", "allocs", "peak", "change"
+    )
+    .unwrap();
+    let percent = |change: Option<f64>| change.map_or("-".into(), |change| format!("{:+.1}%", change));
+    for timing in timings {
+        let change = change(timing, previous);
+        let peak_change = peak_change(timing, previous);
+        let regressed = change.is_some_and(|change| change > threshold);
+        let grew = peak_change.is_some_and(|change| change > threshold);
+        if regressed {
+            regressions.push(format!("{} day{}{}", timing.year, timing.day, timing.step));
+        }
+        if grew {
+            regressions.push(format!("{} day{}{} peak heap", timing.year, timing.day, timing.step));
+        }
+        writeln!(
+            table,
+            "{:>4} {:>3} {:<5} {:>10} {:>10} {:>10} {:>8} {:>8} {:>10} {:>8}{}",
+            timing.year,
+            timing.day,
+            timing.step,
+            format!("{:.1?}", timing.stats.min),
+            format!("{:.1?}", timing.stats.median),
+            format!("{:.1?}", timing.stats.max),
+            percent(change),
+            timing.memory.map_or("-".into(), |memory| memory.allocations.to_string()),
+            timing.memory.map_or("-".into(), |memory| memory::bytes(memory.peak)),
+            percent(peak_change),
+            if regressed || grew { "  REGRESSION" } else { "" }
+        )
+        .unwrap();
+    }
+    (table, regressions)
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+    let stats = Stats::of(&[ms(5), ms(1), ms(3), ms(9), ms(2)]);
+    assert_eq!(stats, Stats { min: ms(1), median: ms(3), max: ms(9) });
+}
+
+#[test]
+fn test_report_flags_regressions() {
+    let ms = Duration::from_millis;
+    let timing = |step: &str, median, peak: Option<u64>| Timing {
+        year: 2023,
+        day: 4,
+        step: step.into(),
+        stats: Stats { min: ms(1), median: ms(median), max: ms(100) },
+        memory: peak.map(|peak| Usage { allocations: 3, bytes: peak, peak }),
+    };
+    let path = std::env::temp_dir().join(format!("rust-advent-test-bench-{}.json", std::process::id()));
+    let mut history = History { runs: vec![] };
+    history.push(3, &[timing("parse", 1, None), timing("p1", 10, Some(1000)), timing("p2", 10, Some(1000))]);
+    history.save(&path).unwrap();
+    let saved = History::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(saved.previous()[&(2023, 4, "p1".into())].memory, Some(Usage { allocations: 3, bytes: 1000, peak: 1000 }));
+    let (table, regressions) = report(
+        &[timing("parse", 1, Some(50)), timing("p1", 11, Some(1100)), timing("p2", 20, Some(3000))],
+        &saved.previous(),
+        25.0,
+    );
+    assert_eq!(regressions, ["2023 day4p2", "2023 day4p2 peak heap"]);
+    let other_year = Timing { year: 2022, ..timing("p2", 20, Some(3000)) };
+    assert!(report(&[other_year], &saved.previous(), 25.0).1.is_empty());
+    assert!(table.contains("+100.0%        3    2.9 KiB  +200.0%  REGRESSION"), "{}", table);
+    assert!(table.contains(" +10.0%        3    1.1 KiB   +10.0%\n"), "{}", table);
+
+    let mut history = saved;
+    for _ in 0..HISTORY_RUNS {
+        history.push(1, &[timing("p1", 12, None)]);
+    }
+    assert_eq!(history.runs.len(), HISTORY_RUNS);
+    assert_eq!(history.previous()[&(2023, 4, "p1".into())].stats.median, ms(12));
+    assert!(!history.previous().contains_key(&(2023, 4, "parse".into())));
+}
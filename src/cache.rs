@@ -0,0 +1,204 @@
+use crate::json::Json;
+use crate::solver::{Answer, Part, Run, Solver};
+use crate::types::MyResult;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
+
+pub const PATH: &str = "cache.json";
+
+fn hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Identifies the running executable by its size and modification time, so
+/// answers of an older build of the solvers are never reused. Hashing the
+/// whole binary would cost more than solving most days.
+pub fn build_id() -> u64 {
+    static BUILD: OnceLock<u64> = OnceLock::new();
+    *BUILD.get_or_init(|| {
+        let exe = env::current_exe().and_then(fs::metadata);
+        let Ok(exe) = exe else { return 0 };
+        let modified = exe.modified().ok().and_then(|time| time.duration_since(UNIX_EPOCH).ok());
+        hash(format!("{} {:?}", exe.len(), modified).as_bytes())
+    })
+}
+
+type Key = (u32, u32, Part, u64);
+
+/// Answers of earlier runs keyed by year, day, part, input hash and build, so a
+/// day whose code and input did not change is not solved again.
+pub struct Cache {
+    enabled: bool,
+    build: u64,
+    entries: Mutex<HashMap<Key, Result<Answer, String>>>,
+    changed: AtomicBool,
+}
+
+impl Cache {
+    /// A cache that never answers and is never written, for `--no-cache`.
+    pub fn disabled() -> Cache {
+        Cache {
+            enabled: false,
+            build: 0,
+            entries: Mutex::default(),
+            changed: AtomicBool::new(false),
+        }
+    }
+
+    /// The entries stored at `path` by this build; a missing file, or one
+    /// that does not parse, is empty.
+    pub fn load(path: &Path) -> MyResult<Cache> {
+        Cache::load_for(path, build_id())
+    }
+
+    fn load_for(path: &Path, build: u64) -> MyResult<Cache> {
+        let cache = Cache {
+            enabled: true,
+            build,
+            entries: Mutex::default(),
+            changed: AtomicBool::new(false),
+        };
+        if !path.exists() {
+            return Ok(cache);
+        }
+        if let Some(entries) = entries_of(&fs::read_to_string(path)?, build) {
+            *cache.entries.lock().unwrap() = entries;
+        }
+        Ok(cache)
+    }
+
+    /// Answers of `solver` on `input`: from the cache when both parts are
+    /// there, in a run without timings, and solved `runs` times otherwise.
+    pub fn run(&self, solver: &dyn Solver, input: &str, runs: usize) -> MyResult<Run> {
+        if !self.enabled {
+            return solver.run(input, runs);
+        }
+        let hashed = hash(input.as_bytes());
+        let key = |part: Part| (solver.year(), solver.day(), part, hashed);
+        {
+            let entries = self.entries.lock().unwrap();
+            if let (Some(p1), Some(p2)) = (entries.get(&key(Part::One)), entries.get(&key(Part::Two))) {
+                return Ok(Run {
+                    year: solver.year(),
+                    day: solver.day(),
+                    answers: [p1.clone(), p2.clone()],
+                    parse: vec![],
+                    parts: [vec![], vec![]],
+                    parse_memory: None,
+                    part_memory: [None, None],
+                });
+            }
+        }
+        let run = solver.run(input, runs)?;
+        let mut entries = self.entries.lock().unwrap();
+        for part in Part::ALL {
+            entries.insert(key(part), run.answers[part.index()].clone());
+        }
+        self.changed.store(true, Ordering::Relaxed);
+        Ok(run)
+    }
+
+    /// Writes the entries to `path` if anything was added since loading.
+    pub fn save(&self, path: &Path) -> MyResult<()> {
+        if !self.enabled || !self.changed.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let mut entries: Vec<_> = self.entries.lock().unwrap().clone().into_iter().collect();
+        entries.sort_by_key(|((year, day, part, input), _)| (*year, *day, part.index(), *input));
+        let json = Json::Object(vec![
+            ("build".into(), Json::from(format!("{:016x}", self.build).as_str())),
+            ("answers".into(), Json::Array(entries.iter().map(entry_json).collect())),
+        ]);
+        // Written aside and renamed over the old file, so that runs saving
+        // at the same time replace it whole instead of mixing their writes.
+        let temp = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&temp, json.to_string() + "\n")?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })?;
+        Ok(())
+    }
+}
+
+/// The entries of a saved cache if it was saved by `build`, and none if it
+/// was saved by another build or does not parse.
+fn entries_of(text: &str, build: u64) -> Option<HashMap<Key, Result<Answer, String>>> {
+    let json = Json::parse(text).ok()?;
+    if json.get("build")?.as_str()? != format!("{:016x}", build) {
+        return None;
+    }
+    json.get("answers")?.as_array()?.iter().map(entry_of).collect()
+}
+
+fn entry_json(((year, day, part, input), answer): &(Key, Result<Answer, String>)) -> Json {
+    let answer = match answer {
+        Ok(answer) => ("answer".into(), Json::from(answer.to_string().as_str())),
+        Err(e) => ("error".into(), Json::from(e.as_str())),
+    };
+    Json::Object(vec![
+        ("year".into(), Json::from(*year as u64)),
+        ("day".into(), Json::from(*day as u64)),
+        ("part".into(), Json::from(part.index() as u64 + 1)),
+        ("input".into(), Json::from(format!("{:016x}", input).as_str())),
+        answer,
+    ])
+}
+
+fn entry_of(json: &Json) -> Option<(Key, Result<Answer, String>)> {
+    let year = json.get("year")?.as_u64()? as u32;
+    let day = json.get("day")?.as_u64()? as u32;
+    let part = match json.get("part")?.as_u64()? {
+        1 => Part::One,
+        2 => Part::Two,
+        _ => return None,
+    };
+    let input = u64::from_str_radix(json.get("input")?.as_str()?, 16).ok()?;
+    let answer = match (json.get("answer"), json.get("error")) {
+        (Some(answer), _) => Ok(answer.as_str()?.parse().ok()?),
+        (None, Some(e)) => Err(e.as_str()?.to_string()),
+        (None, None) => return None,
+    };
+    Some(((year, day, part, input), answer))
+}
+
+#[test]
+fn test_cache() {
+    use crate::y2023::day1::Day1;
+
+    let path = env::temp_dir().join(format!("rust-advent-cache-{}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let cache = Cache::load_for(&path, 7).unwrap();
+    let run = cache.run(&Day1, "a1b2c\n", 1).unwrap();
+    assert_eq!(run.answers[0], Ok(12));
+    assert_eq!(run.parse.len(), 1);
+    cache.save(&path).unwrap();
+
+    let cache = Cache::load_for(&path, 7).unwrap();
+    let run = cache.run(&Day1, "a1b2c\n", 1).unwrap();
+    assert_eq!(run.answers, [Ok(12), Ok(12)]);
+    assert!(run.parse.is_empty(), "answered from the cache");
+    assert_eq!(cache.run(&Day1, "a1b3c\n", 1).unwrap().answers[0], Ok(13));
+
+    // Another build or a disabled cache solves again.
+    assert_eq!(Cache::load_for(&path, 8).unwrap().run(&Day1, "a1b2c\n", 1).unwrap().parse.len(), 1);
+    assert_eq!(Cache::disabled().run(&Day1, "a1b2c\n", 1).unwrap().parse.len(), 1);
+
+    // A broken file, such as one cut short, is an empty cache and is
+    // replaced on the next save.
+    fs::write(&path, "{\"build\": \"0000000000000007\", \"answers\": [").unwrap();
+    let cache = Cache::load_for(&path, 7).unwrap();
+    assert_eq!(cache.run(&Day1, "a1b2c\n", 1).unwrap().parse.len(), 1);
+    cache.save(&path).unwrap();
+    assert!(Cache::load_for(&path, 7).unwrap().run(&Day1, "a1b2c\n", 1).unwrap().parse.is_empty());
+    assert!(!path.with_extension(format!("{}.tmp", std::process::id())).exists());
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,143 @@
+use crate::gen::{self, Size};
+use crate::solver::{Answer, Part};
+use crate::types::MyResult;
+use std::fmt;
+use std::ops::Range;
+
+/// A slower, more obviously correct way to solve one part of a day, checked
+/// against the registered solver of its year and day.
+pub struct Reference {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub name: &'static str,
+    pub solve: fn(&str) -> MyResult<Answer>,
+}
+
+/// The references of every calendar.
+pub fn references() -> impl Iterator<Item = &'static Reference> {
+    crate::YEARS.iter().flat_map(|year| year.references)
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} day{}{} {}", self.year, self.day, self.part, self.name)
+    }
+}
+
+/// Answers that disagree. Both sides failing counts as agreeing.
+#[derive(Debug, PartialEq)]
+pub struct Divergence {
+    pub reference: Result<Answer, String>,
+    pub solver: Result<Answer, String>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |answer: &Result<Answer, String>| match answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => format!("error: {}", e),
+        };
+        write!(f, "reference {}, solver {}", show(&self.reference), show(&self.solver))
+    }
+}
+
+pub fn compare(reference: &Reference, input: &str) -> MyResult<Option<Divergence>> {
+    let reference_answer = (reference.solve)(input).map_err(|e| e.to_string());
+    let solver_answer = match crate::solver(reference.year, reference.day)?.run(input, 1) {
+        Ok(run) => run.answers[reference.part.index()].clone(),
+        Err(e) => Err(e.to_string()),
+    };
+    Ok(match (&reference_answer, &solver_answer) {
+        (Ok(a), Ok(b)) if a == b => None,
+        (Err(_), Err(_)) => None,
+        _ => Some(Divergence { reference: reference_answer, solver: solver_answer }),
+    })
+}
+
+/// A generated input on which a reference and the solver disagree.
+#[derive(Debug)]
+pub struct Case {
+    pub seed: u64,
+    pub size: Size,
+    pub divergence: Divergence,
+}
+
+/// Sizes one step smaller than `size`, one knob at a time.
+fn smaller(size: &Size) -> Vec<Size> {
+    let mut sizes = vec![];
+    let mut knob = |get: fn(&mut Size) -> &mut usize| {
+        let mut value = size.clone();
+        let current = *get(&mut value);
+        for next in [current / 2, current.saturating_sub(1)] {
+            if next < current {
+                *get(&mut value) = next;
+                sizes.push(value.clone());
+            }
+        }
+    };
+    knob(|size| &mut size.lines);
+    knob(|size| &mut size.width);
+    knob(|size| &mut size.ranges);
+    knob(|size| &mut size.winners);
+    knob(|size| &mut size.drawn);
+    if let Some(max) = size.max.filter(|max| *max > 1) {
+        sizes.push(Size { max: Some(max / 2), ..size.clone() });
+    }
+    sizes
+}
+
+/// Shrinks the generator sizes of a diverging `seed` for as long as the
+/// smaller input still diverges.
+pub fn shrink(reference: &Reference, seed: u64, mut size: Size, mut divergence: Divergence) -> MyResult<Case> {
+    'shrink: loop {
+        for candidate in smaller(&size) {
+            let input = gen::input(reference.year, reference.day, seed, &candidate)?;
+            if let Some(found) = compare(reference, &input)? {
+                (size, divergence) = (candidate, found);
+                continue 'shrink;
+            }
+        }
+        return Ok(Case { seed, size, divergence });
+    }
+}
+
+/// The first of `seeds` whose generated input diverges, shrunk.
+pub fn search(reference: &Reference, seeds: Range<u64>, size: &Size) -> MyResult<Option<Case>> {
+    for seed in seeds {
+        let input = gen::input(reference.year, reference.day, seed, size)?;
+        if let Some(divergence) = compare(reference, &input)? {
+            return Ok(Some(shrink(reference, seed, size.clone(), divergence)?));
+        }
+    }
+    Ok(None)
+}
+
+#[test]
+fn test_references_agree() {
+    let size = Size { lines: 12, width: 16, ranges: 6, winners: 5, drawn: 8, max: None };
+    for reference in references() {
+        let case = search(reference, 0..30, &size).unwrap();
+        assert!(case.is_none(), "{}: {:?}", reference, case);
+        let demo = crate::examples::demo(reference.year, reference.day).unwrap();
+        assert_eq!(compare(reference, &demo).unwrap(), None, "{}", reference);
+    }
+}
+
+#[test]
+fn test_shrinks_divergence() {
+    use crate::y2023::day4;
+
+    let wrong = Reference {
+        year: 2023,
+        day: 4,
+        part: Part::One,
+        name: "wrong past three cards",
+        solve: |input| Ok(day4::points_naive(input)? + u64::from(input.lines().count() > 3)),
+    };
+    let case = search(&wrong, 0..10, &Size::default()).unwrap().unwrap();
+    assert_eq!(case.seed, 0);
+    assert_eq!(case.size.lines, 4);
+    assert_eq!((case.size.winners, case.size.drawn), (0, 0));
+    assert_eq!(case.divergence.to_string(), "reference 1, solver 0");
+}
@@ -0,0 +1,120 @@
+use crate::answers::Answers;
+use crate::inputs;
+use crate::solver::{Answer, Part, Run};
+use crate::types::MyResult;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+/// One example input with the answers it is expected to give.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub expected: Answers,
+    pub input: String,
+}
+
+pub fn path(year: u32, day: u32) -> PathBuf {
+    inputs::dir(year).join(format!("examples{}.txt", day))
+}
+
+/// Reads the examples of a day. Each starts with a `=== name` line, then
+/// the expected answers in the answers file format, then `---` and the input
+/// up to the next example. Text before the first example is a comment.
+pub fn parse(text: &str) -> MyResult<Vec<Example>> {
+    let mut chunks: Vec<(&str, Vec<&str>)> = vec![];
+    for line in text.lines() {
+        match (line.strip_prefix("=== "), chunks.last_mut()) {
+            (Some(name), _) => chunks.push((name.trim(), vec![])),
+            (None, Some((_, lines))) => lines.push(line),
+            (None, None) => {}
+        }
+    }
+    chunks
+        .into_iter()
+        .map(|(name, lines)| {
+            let split = lines
+                .iter()
+                .position(|line| line.trim() == "---")
+                .ok_or_else(|| format!("example '{}': no '---' before its input", name))?;
+            let mut input = &lines[split + 1..];
+            while let [rest @ .., last] = input {
+                if !last.trim().is_empty() {
+                    break;
+                }
+                input = rest;
+            }
+            let expected = lines[..split]
+                .join("\n")
+                .parse()
+                .map_err(|e| format!("example '{}': {}", name, e))?;
+            Ok(Example { name: name.into(), expected, input: input.join("\n") + "\n" })
+        })
+        .collect()
+}
+
+/// Examples of `day`, none if the day has no examples file.
+pub fn load(year: u32, day: u32) -> MyResult<Vec<Example>> {
+    let path = path(year, day);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    parse(&fs::read_to_string(&path)?).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+/// The input of the example named `demo` of `day`.
+pub fn demo(year: u32, day: u32) -> MyResult<String> {
+    let demo = load(year, day)?.into_iter().find(|example| example.name == "demo");
+    Ok(demo.ok_or_else(|| format!("{}: no demo example", path(year, day).display()))?.input)
+}
+
+/// The outcome of one part with a known answer.
+#[derive(Debug, PartialEq)]
+pub struct Check {
+    pub part: Part,
+    pub expected: Answer,
+    pub actual: Result<Answer, String>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.actual == Ok(self.expected)
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.actual {
+            Ok(actual) if *actual == self.expected => write!(f, "{}: ok {}", self.part, actual),
+            Ok(actual) => write!(f, "{}: expected {}, got {}", self.part, self.expected, actual),
+            Err(e) => write!(f, "{}: expected {}, got error: {}", self.part, self.expected, e),
+        }
+    }
+}
+
+/// Compares each part of `run` that has an expected answer.
+pub fn check(run: &Run, expected: &Answers) -> Vec<Check> {
+    Part::ALL
+        .into_iter()
+        .filter_map(|part| {
+            let expected = expected.get(part)?;
+            let actual = run.answers[part.index()].clone();
+            Some(Check { part, expected, actual })
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse() {
+    let text = "# comment\n=== demo\np1: 3\n---\n1\n2\n\n=== only p2\n# overlapping words\np2: 82\n---\neightwo\n";
+    let examples = parse(text).unwrap();
+    assert_eq!(examples.len(), 2);
+    assert_eq!(examples[0].name, "demo");
+    assert_eq!(examples[0].expected, Answers { p1: Some(3), p2: None });
+    assert_eq!(examples[0].input, "1\n2\n");
+    assert_eq!(examples[1].expected, Answers { p1: None, p2: Some(82) });
+    assert_eq!(examples[1].input, "eightwo\n");
+    let err = parse("=== bad\np1: 3\n1\n---\n1\n").unwrap_err();
+    assert_eq!(err.to_string(), "example 'bad': '1'?");
+    assert!(parse("=== bad\np1: 3\n").is_err());
+}
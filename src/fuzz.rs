@@ -0,0 +1,286 @@
+use crate::examples;
+use crate::gen::Rng;
+use crate::inputs;
+use crate::runner::panic_message;
+use crate::solver::Solver;
+use crate::types::MyResult;
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+/// Mutated inputs are cut down to this many bytes.
+const MAX_LEN: usize = 64 * 1024;
+
+pub fn corpus_dir(year: u32, day: u32) -> PathBuf {
+    PathBuf::from(format!("fuzz/corpus/{}/day{}", year, day))
+}
+
+pub fn crash_dir() -> PathBuf {
+    PathBuf::from("fuzz/crashes")
+}
+
+fn hash(input: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Writes `input` under `dir`, named by its hash so a repeat is not stored twice.
+pub fn save(dir: &Path, prefix: &str, input: &str) -> MyResult<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}{:016x}.txt", prefix, hash(input)));
+    fs::write(&path, input)?;
+    Ok(path)
+}
+
+/// The inputs of `day` stored under `input/<year>/`: the real one and every
+/// example.
+pub fn seeds(year: u32, day: u32) -> MyResult<Vec<String>> {
+    let mut seeds = vec![];
+    if let Ok(input) = fs::read_to_string(inputs::dir(year).join(format!("input{}.txt", day))) {
+        seeds.push(input);
+    }
+    seeds.extend(examples::load(year, day)?.into_iter().map(|example| example.input));
+    Ok(seeds)
+}
+
+/// The corpus of `day`, first filled from `seeds` when it is empty.
+pub fn load_corpus(year: u32, day: u32) -> MyResult<Vec<String>> {
+    let dir = corpus_dir(year, day);
+    if !dir.exists() {
+        for seed in seeds(year, day)? {
+            save(&dir, "", &seed)?;
+        }
+    }
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)?.map(|entry| Ok(entry?.path())).collect::<MyResult<_>>()?;
+    paths.sort();
+    paths.iter().map(|path| Ok(fs::read_to_string(path)?)).collect()
+}
+
+/// Parses and solves `input`. Errors are fine and describe the outcome; a
+/// panic, overflow included when built with debug assertions, is a crash,
+/// and so is a model that does not print back to an equal one.
+pub fn execute(solver: &dyn Solver, input: &str) -> Result<String, String> {
+    let run = panic::catch_unwind(AssertUnwindSafe(|| solver.run(input, 1)))
+        .map_err(|payload| format!("panicked: {}", panic_message(payload)))?;
+    if run.is_ok() {
+        solver.round_trip(input).map_err(|e| format!("round trip: {}", e))?;
+    }
+    Ok(match run {
+        Err(e) => format!("parse: {}", kind(&e.to_string())),
+        Ok(run) => run
+            .answers
+            .iter()
+            .map(|answer| match answer {
+                Ok(_) => "ok".to_string(),
+                Err(e) => kind(e),
+            })
+            .collect::<Vec<_>>()
+            .join(" / "),
+    })
+}
+
+/// Whether parsing and solving `input`, or printing its model back, panics.
+/// Errors and models that print back differently do not count.
+pub fn panics(solver: &dyn Solver, input: &str) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        if solver.run(input, 1).is_ok() {
+            let _ = solver.round_trip(input);
+        }
+    }))
+    .is_err()
+}
+
+thread_local! {
+    /// Whether this thread is inside [`quietly`].
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Puts the flag of [`quietly`] back, even when `f` panics.
+struct Restore(bool);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        QUIET.with(|quiet| quiet.set(self.0));
+    }
+}
+
+/// Runs `f` with the panic hook silenced on this thread, for runs that
+/// expect to catch panics of the solvers. The hook belongs to the whole
+/// process, so it is replaced only once, by one that asks the thread.
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.try_with(Cell::get).unwrap_or(false) {
+                hook(info);
+            }
+        }));
+    });
+    let _restore = Restore(QUIET.with(|quiet| quiet.replace(true)));
+    f()
+}
+
+#[test]
+fn test_quietly() {
+    let caught = quietly(|| quietly(|| panic::catch_unwind(|| panic!("expected")).is_err()));
+    assert!(caught);
+    assert!(!QUIET.with(Cell::get));
+    let _ = panic::catch_unwind(|| quietly(|| panic!("expected")));
+    assert!(!QUIET.with(Cell::get));
+}
+
+/// An error message without the parts quoted from the input, so that
+/// errors of one kind count as one outcome.
+pub fn kind(message: &str) -> String {
+    let message = message.split('\'').next().unwrap_or_default();
+    message.chars().filter(|c| !c.is_ascii_digit()).collect()
+}
+
+const TOKENS: [&str; 12] = [
+    "0", "1", "-1", "4294967295", "4294967296", "18446744073709551616", " ", ":", "|", ";", "\n", "",
+];
+
+fn char_boundary(input: &str, rng: &mut Rng) -> usize {
+    let mut at = rng.below(input.len() as u64 + 1) as usize;
+    while !input.is_char_boundary(at) {
+        at -= 1;
+    }
+    at
+}
+
+/// One random edit of `input`, sometimes spliced with another corpus entry.
+pub fn mutate(rng: &mut Rng, input: &str, corpus: &[String]) -> String {
+    let mut lines: Vec<&str> = input.split_inclusive('\n').collect();
+    let mut output = match rng.below(8) {
+        0 if !lines.is_empty() => {
+            lines.remove(rng.below(lines.len() as u64) as usize);
+            lines.concat()
+        }
+        1 if !lines.is_empty() => {
+            let line = *rng.pick(&lines);
+            lines.insert(rng.below(lines.len() as u64 + 1) as usize, line);
+            lines.concat()
+        }
+        2 if lines.len() > 1 => {
+            let i = rng.below(lines.len() as u64) as usize;
+            let j = rng.below(lines.len() as u64) as usize;
+            lines.swap(i, j);
+            lines.concat()
+        }
+        3 if !corpus.is_empty() => {
+            let other = rng.pick(corpus);
+            let at = char_boundary(input, rng);
+            let from = char_boundary(other, rng);
+            input[..at].to_string() + &other[from..]
+        }
+        4 => {
+            let start = char_boundary(input, rng);
+            let end = char_boundary(&input[start..], rng) / 4 + start;
+            let end = (end..=input.len()).find(|end| input.is_char_boundary(*end)).unwrap_or(input.len());
+            input[..start].to_string() + &input[end..]
+        }
+        5 => input[..char_boundary(input, rng)].to_string(),
+        6 if !input.is_empty() => {
+            // Replace one character with another one already in the input.
+            let at = char_boundary(input, rng);
+            let chars: Vec<char> = input.chars().collect();
+            let mut output = input.to_string();
+            if let Some(old) = input[at..].chars().next() {
+                output.replace_range(at..at + old.len_utf8(), &rng.pick(&chars).to_string());
+            }
+            output
+        }
+        _ => {
+            let mut output = input.to_string();
+            let at = char_boundary(input, rng);
+            let token = *rng.pick(&TOKENS);
+            output.insert_str(at, token);
+            output
+        }
+    };
+    if output.len() > MAX_LEN {
+        output.truncate(char_boundary(&output[..MAX_LEN], rng));
+    }
+    output
+}
+
+/// What one fuzzing session found.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub runs: usize,
+    /// Inputs with an outcome not seen before, worth keeping in the corpus.
+    pub new_inputs: Vec<String>,
+    /// Inputs that crashed, with the panic message.
+    pub crashes: Vec<(String, String)>,
+}
+
+/// Runs `runs` mutations of the corpus. An input is kept when it leads to an
+/// outcome not seen before, the closest to coverage we get without
+/// instrumentation; crashing inputs are reported once per message.
+pub fn fuzz(solver: &dyn Solver, corpus: &[String], seed: u64, runs: usize) -> Report {
+    let rng = &mut Rng::new(seed);
+    let mut corpus = corpus.to_vec();
+    let mut outcomes: HashSet<String> = corpus.iter().filter_map(|input| execute(solver, input).ok()).collect();
+    let mut crashes: HashSet<String> = HashSet::new();
+    let mut report = Report::default();
+    quietly(|| {
+        for _ in 0..runs {
+            let base = match corpus.is_empty() {
+                true => String::new(),
+                false => rng.pick(&corpus).clone(),
+            };
+            let mut input = base;
+            for _ in 0..rng.between(1, 4) {
+                input = mutate(rng, &input, &corpus);
+            }
+            report.runs += 1;
+            match execute(solver, &input) {
+                Ok(outcome) => {
+                    if outcomes.insert(outcome) {
+                        corpus.push(input.clone());
+                        report.new_inputs.push(input);
+                    }
+                }
+                Err(message) => {
+                    if crashes.insert(message.clone()) {
+                        report.crashes.push((input, message));
+                    }
+                }
+            }
+        }
+    });
+    report
+}
+
+#[test]
+fn test_fuzz_finds_no_crashes() {
+    for solver in crate::y2023::DAYS {
+        let corpus = seeds(solver.year(), solver.day()).unwrap();
+        let report = fuzz(*solver, &corpus, 1, 300);
+        assert_eq!(report.runs, 300);
+        assert!(report.crashes.is_empty(), "day{}: {:?}", solver.day(), report.crashes);
+    }
+}
+
+#[test]
+fn test_fuzz_regressions() {
+    let crashed = [
+        (2, ":\n"),
+        (2, "Ga 4: 1 green, 3 red\n"),
+        (2, "Game 1: 3 blue, 4 re\n"),
+        (5, "seeds: 79 14\n\nseed-to-soil map:\n50 98\n"),
+        (5, "seeds: 79 14\n\ns eed-to-soil map:\n50 98 2\n"),
+        (5, "seeds: 9223372036854775807 1\n\nseed-to-soil map:\n50 98 2\n"),
+    ];
+    for (day, input) in crashed {
+        let solver = crate::solver(2023, day).unwrap();
+        assert!(execute(solver, input).is_ok(), "day{}: {:?}", day, input);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::types::MyResult;
+
+/// Small seeded generator (xorshift64*); the same seed always yields the
+/// same inputs.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // splitmix64 spreads small seeds and keeps the state away from 0.
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        Rng { state: (z ^ (z >> 31)) | 1 }
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+    /// A value in `0..n`, or 0 when `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        match n {
+            0 => 0,
+            n => self.next_u64() % n,
+        }
+    }
+    /// A value in `low..=high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Size knobs shared by the generators; each day uses the ones that apply.
+#[derive(Clone, Debug)]
+pub struct Size {
+    /// Lines of day 1, games of day 2, grid rows of day 3, cards of day 4,
+    /// seed ranges of day 5.
+    pub lines: usize,
+    /// Characters per line of days 1 and 3.
+    pub width: usize,
+    /// Translations in each map of day 5.
+    pub ranges: usize,
+    /// Winning numbers per card of day 4.
+    pub winners: usize,
+    /// Drawn numbers per card of day 4.
+    pub drawn: usize,
+    /// Largest cube count of day 2 (20 by default) or number of day 5
+    /// (2^32 by default).
+    pub max: Option<u64>,
+}
+
+impl Default for Size {
+    fn default() -> Size {
+        Size {
+            lines: 100,
+            width: 40,
+            ranges: 30,
+            winners: 10,
+            drawn: 25,
+            max: None,
+        }
+    }
+}
+
+impl Size {
+    /// The `gen` flags that give these sizes.
+    pub fn flags(&self) -> String {
+        let mut flags = format!(
+            "--lines {} --width {} --ranges {} --winners {} --drawn {}",
+            self.lines, self.width, self.ranges, self.winners, self.drawn
+        );
+        if let Some(max) = self.max {
+            flags += &format!(" --max {}", max);
+        }
+        flags
+    }
+}
+
+/// A random input for `day` of `year` that its solver accepts.
+pub fn input(year: u32, day: u32, seed: u64, size: &Size) -> MyResult<String> {
+    let generate = crate::year(year)?.generate;
+    let input = generate(day, &mut Rng::new(seed), size);
+    Ok(input.ok_or_else(|| format!("no generator for {} day {}", year, day))?)
+}
+
+#[test]
+fn test_generated_inputs_solve() {
+    use crate::solver::Solver;
+    use crate::y2023::{day5, DAYS};
+
+    let sizes = [
+        Size::default(),
+        Size { lines: 1, width: 1, ranges: 1, winners: 1, drawn: 1, max: Some(2) },
+        Size { lines: 20, width: 200, ranges: 100, winners: 60, drawn: 60, max: None },
+    ];
+    for solver in DAYS {
+        for (seed, size) in sizes.iter().enumerate() {
+            let input = input(2023, solver.day(), seed as u64, size).unwrap();
+            assert_eq!(input, self::input(2023, solver.day(), seed as u64, size).unwrap());
+            let run = solver.run(&input, 1).unwrap();
+            for answer in &run.answers {
+                assert!(answer.is_ok(), "day{} seed {}: {:?}\n{}", solver.day(), seed, answer, input);
+            }
+        }
+    }
+    // --max past what day 5 parses is clamped to it.
+    let huge = input(2023, 5, 0, &Size { max: Some(u64::MAX), ..Size::default() }).unwrap();
+    let run = day5::Day5.run(&huge, 1).unwrap();
+    assert!(run.answers.iter().all(|answer| answer.is_ok()), "{:?}", run.answers);
+    assert!(input(2023, 25, 0, &Size::default()).is_err());
+    assert!(input(2022, 1, 0, &Size::default()).is_err());
+
+    // Cube counts this large make the power of a game overflow.
+    let size = Size { max: Some(1 << 40), ..Size::default() };
+    let run = DAYS[1].run(&input(2023, 2, 0, &size).unwrap(), 1).unwrap();
+    assert_eq!(run.answers[1], Err("arithmetic overflow".into()));
+}
@@ -0,0 +1,136 @@
+use crate::inputs;
+use crate::json::Json;
+use crate::site::Verdict;
+use crate::solver::{Answer, Part};
+use crate::types::MyResult;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Guess {
+    pub part: Part,
+    pub answer: Answer,
+    pub verdict: Verdict,
+    pub timestamp: u64,
+}
+
+/// Every answer submitted for a day, with what the site said about it.
+#[derive(Debug, Default)]
+pub struct Guesses {
+    pub guesses: Vec<Guess>,
+}
+
+impl Guesses {
+    pub fn path(year: u32, day: u32) -> PathBuf {
+        inputs::dir(year).join(format!("guesses{}.json", day))
+    }
+    pub fn load(path: &Path) -> MyResult<Guesses> {
+        if !path.exists() {
+            return Ok(Guesses::default());
+        }
+        let json = Json::parse(&fs::read_to_string(path)?)?;
+        let guesses = json
+            .get("guesses")
+            .and_then(Json::as_array)
+            .ok_or("guesses?")?
+            .iter()
+            .map(|guess| -> MyResult<Guess> {
+                let field = |key| guess.get(key).ok_or_else(|| format!("guess {}?", key));
+                Ok(Guess {
+                    part: match field("part")?.as_u64() {
+                        Some(1) => Part::One,
+                        Some(2) => Part::Two,
+                        _ => return Err("guess part?".into()),
+                    },
+                    answer: field("answer")?.as_str().ok_or("guess answer?")?.parse()?,
+                    verdict: field("verdict")?.as_str().ok_or("guess verdict?")?.parse()?,
+                    timestamp: field("timestamp")?.as_u64().ok_or("guess timestamp?")?,
+                })
+            })
+            .collect::<MyResult<_>>()?;
+        Ok(Guesses { guesses })
+    }
+    pub fn save(&self, path: &Path) -> MyResult<()> {
+        let guesses = self
+            .guesses
+            .iter()
+            .map(|guess| {
+                Json::Object(vec![
+                    ("part".into(), Json::from(guess.part.index() as u64 + 1)),
+                    // Kept as a string, answers may not fit a JSON number exactly.
+                    ("answer".into(), Json::from(guess.answer.to_string().as_str())),
+                    ("verdict".into(), Json::from(guess.verdict.to_string().as_str())),
+                    ("timestamp".into(), Json::from(guess.timestamp)),
+                ])
+            })
+            .collect();
+        let json = Json::Object(vec![("guesses".into(), Json::Array(guesses))]);
+        fs::write(path, json.to_string() + "\n")?;
+        Ok(())
+    }
+    pub fn record(&mut self, part: Part, answer: Answer, verdict: Verdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.guesses.push(Guess { part, answer, verdict, timestamp });
+    }
+    fn judged(&self, part: Part) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |guess| guess.part == part && guess.verdict.is_judged())
+    }
+    pub fn correct(&self, part: Part) -> Option<Answer> {
+        self.judged(part)
+            .find(|guess| guess.verdict == Verdict::Correct)
+            .map(|guess| guess.answer)
+    }
+    /// Why `answer` should not be submitted for `part`, if what the site
+    /// said about earlier guesses already rules it out.
+    pub fn refusal(&self, part: Part, answer: Answer) -> Option<String> {
+        if let Some(correct) = self.correct(part) {
+            return Some(format!("{} is already solved with {}", part, correct));
+        }
+        if let Some(guess) = self.judged(part).find(|guess| guess.answer == answer) {
+            return Some(format!("{} was already guessed and was {}", answer, guess.verdict));
+        }
+        let too_high = self
+            .judged(part)
+            .filter(|guess| guess.verdict == Verdict::TooHigh)
+            .map(|guess| guess.answer)
+            .min();
+        if let Some(high) = too_high.filter(|high| answer >= *high) {
+            return Some(format!("{} is not below {}, which was too high", answer, high));
+        }
+        let too_low = self
+            .judged(part)
+            .filter(|guess| guess.verdict == Verdict::TooLow)
+            .map(|guess| guess.answer)
+            .max();
+        if let Some(low) = too_low.filter(|low| answer <= *low) {
+            return Some(format!("{} is not above {}, which was too low", answer, low));
+        }
+        None
+    }
+}
+
+#[test]
+fn test_refusals() {
+    let mut guesses = Guesses::default();
+    guesses.record(Part::One, 500, Verdict::TooHigh);
+    guesses.record(Part::One, 100, Verdict::TooLow);
+    guesses.record(Part::One, 300, Verdict::Wrong);
+    guesses.record(Part::One, 250, Verdict::Wait(30));
+    assert_eq!(guesses.refusal(Part::One, 300).unwrap(), "300 was already guessed and was wrong");
+    assert_eq!(guesses.refusal(Part::One, 600).unwrap(), "600 is not below 500, which was too high");
+    assert_eq!(guesses.refusal(Part::One, 90).unwrap(), "90 is not above 100, which was too low");
+    assert_eq!(guesses.refusal(Part::One, 250), None);
+    assert_eq!(guesses.refusal(Part::Two, 600), None);
+    guesses.record(Part::One, 250, Verdict::Correct);
+    assert_eq!(guesses.refusal(Part::One, 260).unwrap(), "p1 is already solved with 250");
+
+    let path = std::env::temp_dir().join(format!("rust-advent-guesses-{}.json", std::process::id()));
+    guesses.save(&path).unwrap();
+    assert_eq!(Guesses::load(&path).unwrap().guesses, guesses.guesses);
+    fs::remove_file(&path).unwrap();
+}
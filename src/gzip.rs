@@ -0,0 +1,303 @@
+use crate::types::MyResult;
+use std::io::{self, BufRead, Read};
+
+/// Decompressed inputs stop here, so that a small file cannot expand to
+/// fill memory; real inputs are a few tens of KiB.
+pub const MAX_OUTPUT: usize = 64 << 20;
+
+/// Gzip files start with these two bytes.
+pub fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0x1f, 0x8b])
+}
+
+/// The contents of a gzip stream, all members of it concatenated; the length
+/// and CRC of each member are checked. The compressed bytes are taken from
+/// `reader` as they are needed rather than read in first. Inflate is written
+/// out here because the crate keeps to std and builds offline with nothing
+/// but a toolchain; inputs are small and read once, so it is plain rather
+/// than fast.
+pub fn decompress(reader: impl BufRead) -> MyResult<Vec<u8>> {
+    decompress_at_most(reader, MAX_OUTPUT)
+}
+
+/// Like [`decompress`], failing once the output would pass `limit` bytes.
+fn decompress_at_most(mut reader: impl BufRead, limit: usize) -> MyResult<Vec<u8>> {
+    let mut output = vec![];
+    loop {
+        let start = output.len();
+        header(&mut reader)?;
+        let mut bits = Bits { reader: &mut reader, buffer: 0, count: 0 };
+        inflate(&mut bits, &mut output, limit)?;
+        let trailer = bits.bytes::<8>().map_err(|_| "gzip: truncated trailer")?;
+        let word = |at: usize| u32::from_le_bytes([trailer[at], trailer[at + 1], trailer[at + 2], trailer[at + 3]]);
+        if word(0) != crc32(&output[start..]) {
+            return Err("gzip: bad crc".into());
+        }
+        if word(4) != (output.len() - start) as u32 {
+            return Err("gzip: bad length".into());
+        }
+        if reader.fill_buf()?.is_empty() {
+            return Ok(output);
+        }
+    }
+}
+
+/// Reads past the header of a member.
+fn header(reader: &mut impl BufRead) -> MyResult<()> {
+    let mut header = [0u8; 10];
+    reader.read_exact(&mut header).map_err(|_| "gzip: not a deflate gzip member")?;
+    if !is_gzip(&header) || header[2] != 8 {
+        return Err("gzip: not a deflate gzip member".into());
+    }
+    let truncated = |_| "gzip: truncated header";
+    let flags = header[3];
+    if flags & 4 != 0 {
+        let mut extra = [0u8; 2];
+        reader.read_exact(&mut extra).map_err(truncated)?;
+        let length = u64::from(u16::from_le_bytes(extra));
+        if io::copy(&mut reader.take(length), &mut io::sink())? != length {
+            return Err("gzip: truncated header".into());
+        }
+    }
+    // File name and comment, each ending in a zero byte.
+    for flag in [8, 16] {
+        if flags & flag != 0 {
+            let mut text = vec![];
+            reader.read_until(0, &mut text)?;
+            if text.last() != Some(&0) {
+                return Err("gzip: truncated header".into());
+            }
+        }
+    }
+    if flags & 2 != 0 {
+        reader.read_exact(&mut [0u8; 2]).map_err(truncated)?;
+    }
+    Ok(())
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        *entry = (0..8).fold(n as u32, |c, _| if c & 1 == 1 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 });
+    }
+    !bytes.iter().fold(!0u32, |crc, b| table[((crc ^ u32::from(*b)) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// Reads the deflate stream least significant bit first, a byte at a time,
+/// so that what follows the stream is left in the reader.
+struct Bits<'a> {
+    reader: &'a mut dyn BufRead,
+    buffer: u32,
+    count: u32,
+}
+
+impl Bits<'_> {
+    fn bits(&mut self, n: u32) -> MyResult<u32> {
+        while self.count < n {
+            let byte = *self.reader.fill_buf()?.first().ok_or("gzip: truncated data")?;
+            self.reader.consume(1);
+            self.buffer |= u32::from(byte) << self.count;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1u64 << n) - 1) as u32;
+        self.buffer >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+    /// Drops the bits left of the current byte, for stored blocks and the
+    /// trailer.
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+    /// The next `N` whole bytes.
+    fn bytes<const N: usize>(&mut self) -> MyResult<[u8; N]> {
+        self.align();
+        let mut bytes = [0u8; N];
+        self.reader.read_exact(&mut bytes).map_err(|_| "gzip: truncated data")?;
+        Ok(bytes)
+    }
+}
+
+/// Canonical Huffman code: how many codes there are of each length, and the
+/// symbols in code order.
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for length in lengths {
+            counts[usize::from(*length)] += 1;
+        }
+        counts[0] = 0;
+        let mut symbols = vec![];
+        for length in 1..16 {
+            for (symbol, _) in lengths.iter().enumerate().filter(|(_, l)| usize::from(**l) == length) {
+                symbols.push(symbol as u16);
+            }
+        }
+        Huffman { counts, symbols }
+    }
+    fn decode(&self, bits: &mut Bits) -> MyResult<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for length in 1..16 {
+            code |= bits.bits(1)? as i32;
+            let count = i32::from(self.counts[length]);
+            if code - first < count {
+                return self.symbols.get((index + code - first) as usize).copied().ok_or("gzip: bad code".into());
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("gzip: bad code".into())
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145,
+    8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+/// Order in which a dynamic block lists the lengths of the code length code.
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+fn inflate(bits: &mut Bits, output: &mut Vec<u8>, limit: usize) -> MyResult<()> {
+    let start = output.len();
+    loop {
+        let last = bits.bits(1)? == 1;
+        match bits.bits(2)? {
+            0 => stored(bits, output, limit)?,
+            1 => {
+                let mut lengths = [8u8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                codes(bits, output, start, limit, &Huffman::new(&lengths), &Huffman::new(&[5; 30]))?;
+            }
+            2 => {
+                let (literals, distances) = dynamic(bits)?;
+                codes(bits, output, start, limit, &literals, &distances)?;
+            }
+            _ => return Err("gzip: bad block type".into()),
+        }
+        if last {
+            return Ok(());
+        }
+    }
+}
+
+fn room(output: &[u8], more: usize, limit: usize) -> MyResult<()> {
+    match output.len() + more <= limit {
+        true => Ok(()),
+        false => Err(format!("gzip: output over {} bytes", limit).into()),
+    }
+}
+
+fn stored(bits: &mut Bits, output: &mut Vec<u8>, limit: usize) -> MyResult<()> {
+    let header = bits.bytes::<4>()?;
+    let length = usize::from(u16::from_le_bytes([header[0], header[1]]));
+    if length != usize::from(!u16::from_le_bytes([header[2], header[3]])) {
+        return Err("gzip: bad stored block length".into());
+    }
+    room(output, length, limit)?;
+    if bits.reader.take(length as u64).read_to_end(output)? != length {
+        return Err("gzip: truncated data".into());
+    }
+    Ok(())
+}
+
+fn dynamic(bits: &mut Bits) -> MyResult<(Huffman, Huffman)> {
+    let literals = bits.bits(5)? as usize + 257;
+    let distances = bits.bits(5)? as usize + 1;
+    let code_lengths = bits.bits(4)? as usize + 4;
+    if literals > 286 || distances > 30 {
+        return Err("gzip: bad counts".into());
+    }
+    let mut lengths = [0u8; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_lengths] {
+        lengths[symbol] = bits.bits(3)? as u8;
+    }
+    let code = Huffman::new(&lengths);
+    let mut lengths = vec![];
+    while lengths.len() < literals + distances {
+        let (length, repeat) = match code.decode(bits)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last().ok_or("gzip: repeat with no length")?, 3 + bits.bits(2)?),
+            17 => (0, 3 + bits.bits(3)?),
+            _ => (0, 11 + bits.bits(7)?),
+        };
+        lengths.extend(std::iter::repeat_n(length, repeat as usize));
+    }
+    if lengths.len() > literals + distances {
+        return Err("gzip: too many lengths".into());
+    }
+    Ok((Huffman::new(&lengths[..literals]), Huffman::new(&lengths[literals..])))
+}
+
+/// Literals and back references until the end of the block. `start` is
+/// where this member's output begins, the furthest back a reference reaches.
+fn codes(
+    bits: &mut Bits,
+    output: &mut Vec<u8>,
+    start: usize,
+    limit: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> MyResult<()> {
+    loop {
+        let symbol = usize::from(literals.decode(bits)?);
+        match symbol {
+            0..=255 => {
+                room(output, 1, limit)?;
+                output.push(symbol as u8)
+            }
+            256 => return Ok(()),
+            _ => {
+                let i = symbol - 257;
+                let base = *LENGTH_BASE.get(i).ok_or("gzip: bad length code")?;
+                let length = usize::from(base) + bits.bits(LENGTH_EXTRA[i].into())? as usize;
+                let i = usize::from(distances.decode(bits)?);
+                let base = *DISTANCE_BASE.get(i).ok_or("gzip: bad distance code")?;
+                let distance = usize::from(base) + bits.bits(DISTANCE_EXTRA[i].into())? as usize;
+                if distance > output.len() - start {
+                    return Err("gzip: distance too far back".into());
+                }
+                room(output, length, limit)?;
+                for _ in 0..length {
+                    output.push(output[output.len() - distance]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decompress() {
+    let demo = crate::examples::demo(2023, 5).unwrap();
+    assert_eq!(decompress(&include_bytes!("../input/2023/demo5.txt.gz")[..]).unwrap(), demo.as_bytes());
+
+    let fixed: &[u8] = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x73\x4f\xcc\x4d\x55\x30\xb4\x52\x30\x56\x48\xca\x29\x4d\xd5\x51\x30\x51\x28\x4a\x4d\xb1\x56\x30\x04\x51\x3a\x0a\x46\x0a\xe9\x45\xa9\xa9\x79\x5c\x00\x8b\xb6\xad\xe9\x26\x00\x00\x00";
+    let stored: &[u8] = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x04\x03\x01\x06\x00\xf9\xff\x31\x61\x62\x63\x32\x0a\xc8\xdc\xad\x0c\x06\x00\x00\x00";
+    assert_eq!(decompress(fixed).unwrap(), b"Game 1: 3 blue, 4 red; 1 red, 2 green\n");
+    assert_eq!(decompress(stored).unwrap(), b"1abc2\n");
+    assert_eq!(decompress(&[stored, fixed].concat()[..]).unwrap(), b"1abc2\nGame 1: 3 blue, 4 red; 1 red, 2 green\n");
+
+    let mut corrupt = stored.to_vec();
+    corrupt[15] = b'x';
+    assert_eq!(decompress(&corrupt[..]).unwrap_err().to_string(), "gzip: bad crc");
+    assert_eq!(decompress(&fixed[..30]).unwrap_err().to_string(), "gzip: truncated data");
+    assert!(decompress(&b"1abc2\n"[..]).is_err());
+
+    assert_eq!(decompress_at_most(fixed, 38).unwrap().len(), 38);
+    assert_eq!(decompress_at_most(fixed, 37).unwrap_err().to_string(), "gzip: output over 37 bytes");
+    assert_eq!(decompress_at_most(stored, 5).unwrap_err().to_string(), "gzip: output over 5 bytes");
+}
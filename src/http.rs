@@ -0,0 +1,190 @@
+use crate::types::MyResult;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const USER_AGENT: &str = "rust-advent (https://github.com/george-aprozeanu/rust-adventc)";
+
+/// How long to wait for a connection, and then for each read or write.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+pub fn get(url: &str, headers: &[(&str, &str)]) -> MyResult<Response> {
+    request("GET", url, headers, None)
+}
+
+pub fn post_form(url: &str, headers: &[(&str, &str)], form: &[(&str, &str)]) -> MyResult<Response> {
+    let body = form
+        .iter()
+        .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    request("POST", url, headers, Some(body))
+}
+
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Plain `http://` is spoken directly over a socket; `https://` is handed to
+/// the system `curl` so the crate needs no TLS dependency.
+fn request(method: &str, url: &str, headers: &[(&str, &str)], body: Option<String>) -> MyResult<Response> {
+    if let Some(rest) = url.strip_prefix("http://") {
+        plain(method, rest, headers, body)
+    } else if url.starts_with("https://") {
+        curl(method, url, headers, body)
+    } else {
+        Err(format!("unsupported url '{}'", url).into())
+    }
+}
+
+/// Connects to the first address `address` resolves to that answers in
+/// time, with reads and writes that give up too.
+fn connect(address: &str) -> io::Result<TcpStream> {
+    let mut error = io::Error::new(io::ErrorKind::NotFound, "no address");
+    for address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(IO_TIMEOUT))?;
+                stream.set_write_timeout(Some(IO_TIMEOUT))?;
+                return Ok(stream);
+            }
+            Err(e) => error = e,
+        }
+    }
+    Err(error)
+}
+
+fn plain(method: &str, rest: &str, headers: &[(&str, &str)], body: Option<String>) -> MyResult<Response> {
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let path = if path.is_empty() { "/" } else { path };
+    let address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+    let mut stream = connect(&address).map_err(|e| format!("{}: {}", address, e))?;
+    // HTTP/1.0 keeps the response unchunked and closes the connection after it.
+    let mut request = format!("{} {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: {}\r\n", method, path, host, USER_AGENT);
+    for (name, value) in headers {
+        request += &format!("{}: {}\r\n", name, value);
+    }
+    if let Some(body) = &body {
+        request += "Content-Type: application/x-www-form-urlencoded\r\n";
+        request += &format!("Content-Length: {}\r\n", body.len());
+    }
+    request += "\r\n";
+    request += body.as_deref().unwrap_or("");
+    stream.write_all(request.as_bytes())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response.split_once("\r\n\r\n").ok_or("http: no header end")?;
+    let status = head
+        .split(' ')
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| format!("http: bad status line '{}'", head.lines().next().unwrap_or("")))?;
+    Ok(Response { status, body: body.into() })
+}
+
+/// A curl config file setting `headers`. Quoted values take backslash
+/// escapes.
+fn curl_config(headers: &[(&str, &str)]) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let header = format!("{}: {}", name, value).replace('\\', "\\\\").replace('"', "\\\"");
+            format!("header = \"{}\"\n", header)
+        })
+        .collect()
+}
+
+/// Headers go to curl as a config on its stdin, so that the session cookie
+/// is not on a command line that any process can read.
+fn curl(method: &str, url: &str, headers: &[(&str, &str)], body: Option<String>) -> MyResult<Response> {
+    let mut command = Command::new("curl");
+    command.args(["-sS", "-K", "-", "-X", method, "-A", USER_AGENT, "-w", "\n%{http_code}"]);
+    command.args(["--connect-timeout", &CONNECT_TIMEOUT.as_secs().to_string()]);
+    command.args(["--speed-time", &IO_TIMEOUT.as_secs().to_string(), "--speed-limit", "1"]);
+    if let Some(body) = body {
+        command.args(["--data", &body]);
+    }
+    command.arg(url).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    let mut child = command.spawn().map_err(|e| format!("curl: {}", e))?;
+    // Dropping stdin once written closes it, which ends the config.
+    child.stdin.take().ok_or("curl: no stdin")?.write_all(curl_config(headers).as_bytes())?;
+    let output = child.wait_with_output().map_err(|e| format!("curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("curl: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+    let output = String::from_utf8(output.stdout)?;
+    let (body, status) = output.rsplit_once('\n').ok_or("curl: no status")?;
+    Ok(Response { status: status.parse()?, body: body.into() })
+}
+
+/// Serves each of `responses` to one connection on a local port and returns
+/// the base url plus a handle yielding the raw requests received.
+#[cfg(test)]
+pub fn mock_server(responses: Vec<(u16, String)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = vec![];
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.strip_prefix("Content-Length: ") {
+                    length = value.trim().parse().unwrap();
+                }
+                request += &line;
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request += &String::from_utf8(content).unwrap();
+            let response = format!("HTTP/1.0 {} X\r\nContent-Length: {}\r\n\r\n{}", status, body.len(), body);
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (url, handle)
+}
+
+#[test]
+fn test_plain_get_and_post() {
+    let (url, server) = mock_server(vec![(200, "hello\n".into()), (404, "nope".into())]);
+    let response = get(&format!("{}/2023/day/1/input", url), &[("Cookie", "session=abc")]).unwrap();
+    assert_eq!((response.status, response.body.as_str()), (200, "hello\n"));
+    let response = post_form(&format!("{}/answer", url), &[], &[("level", "1"), ("answer", "a b&c")]).unwrap();
+    assert_eq!(response.status, 404);
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /2023/day/1/input HTTP/1.0\r\n"), "{}", requests[0]);
+    assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+    assert!(requests[1].ends_with("\r\n\r\nlevel=1&answer=a%20b%26c"), "{}", requests[1]);
+}
+
+#[test]
+fn test_curl_config() {
+    let config = curl_config(&[("Cookie", "session=abc"), ("X-Quote", "a \"b\" \\c")]);
+    assert_eq!(config, "header = \"Cookie: session=abc\"\nheader = \"X-Quote: a \\\"b\\\" \\\\c\"\n");
+}
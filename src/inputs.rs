@@ -0,0 +1,221 @@
+use crate::examples;
+use crate::gzip;
+use crate::types::MyResult;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::PathBuf;
+
+/// The year commands use when no `--year` is given.
+pub const YEAR: u32 = 2023;
+
+/// Inputs, examples and answers of `year` live here.
+pub fn dir(year: u32) -> PathBuf {
+    PathBuf::from(format!("input/{}", year))
+}
+
+/// Identifies one puzzle input. The variant is `input` for the real input,
+/// `demo` for the example, which comes from the `demo` example of the
+/// examples file when there is no demo file, or another name such as
+/// `test_2`, which is stored as `test<day>_2.txt`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InputKey {
+    pub year: u32,
+    pub day: u32,
+    pub variant: String,
+}
+
+impl InputKey {
+    pub fn new(year: u32, day: u32, variant: &str) -> InputKey {
+        InputKey { year, day, variant: variant.into() }
+    }
+    /// The real puzzle input of `day` in `year`.
+    pub fn input(year: u32, day: u32) -> InputKey {
+        InputKey::new(year, day, "input")
+    }
+    pub fn file_name(&self) -> String {
+        match self.variant.split_once('_') {
+            Some((name, suffix)) => format!("{}{}_{}.txt", name, self.day, suffix),
+            None => format!("{}{}.txt", self.variant, self.day),
+        }
+    }
+}
+
+impl fmt::Display for InputKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} day {} {}", self.year, self.day, self.variant)
+    }
+}
+
+/// Reads a whole input from `reader`, decompressing it as it is read if it
+/// is gzipped. The solvers parse the input as one text, so that their
+/// models can borrow from it, so only the compressed bytes stream.
+pub fn read_from(mut reader: impl Read) -> MyResult<String> {
+    let mut magic = vec![];
+    reader.by_ref().take(2).read_to_end(&mut magic)?;
+    let mut reader = BufReader::new(magic.as_slice().chain(reader));
+    let bytes = match gzip::is_gzip(&magic) {
+        true => gzip::decompress(reader)?,
+        false => {
+            let mut bytes = vec![];
+            reader.read_to_end(&mut bytes)?;
+            bytes
+        }
+    };
+    Ok(String::from_utf8(bytes).map_err(|_| "input is not UTF-8")?)
+}
+
+/// Reads the input at `path`, or stdin for `-`.
+pub fn read_path(path: &str) -> MyResult<String> {
+    let input = match path {
+        "-" => read_from(io::stdin().lock()),
+        path => File::open(path).map_err(|e| e.into()).and_then(read_from),
+    };
+    Ok(input.map_err(|e| format!("{}: {}", path, e))?)
+}
+
+/// Source of inputs missing from the store.
+pub trait Fetcher: Sync {
+    fn fetch(&self, key: &InputKey) -> MyResult<String>;
+}
+
+/// Inputs on disk, one directory per year. Missing inputs are fetched once
+/// and cached; an empty file counts as missing, as `new` leaves empty
+/// placeholders behind.
+pub struct InputStore {
+    roots: Vec<(u32, PathBuf)>,
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl Default for InputStore {
+    fn default() -> InputStore {
+        InputStore::new(crate::YEARS.iter().map(|year| (year.year, dir(year.year))).collect())
+    }
+}
+
+impl InputStore {
+    pub fn new(roots: Vec<(u32, PathBuf)>) -> InputStore {
+        InputStore { roots, fetcher: None }
+    }
+    pub fn with_fetcher(mut self, fetcher: impl Fetcher + 'static) -> InputStore {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+    pub fn path(&self, key: &InputKey) -> MyResult<PathBuf> {
+        let (_, root) = self
+            .roots
+            .iter()
+            .find(|(year, _)| *year == key.year)
+            .ok_or_else(|| format!("no inputs for year {}", key.year))?;
+        Ok(root.join(key.file_name()))
+    }
+    /// The files `key` is read from, in the order they are tried: the plain
+    /// one, then the gzipped one.
+    pub fn paths(&self, key: &InputKey) -> MyResult<[PathBuf; 2]> {
+        let path = self.path(key)?;
+        let gz = path.with_extension("txt.gz");
+        Ok([path, gz])
+    }
+    /// The file holding `key`, the gzipped one if only that is there.
+    fn stored(&self, key: &InputKey) -> MyResult<Option<PathBuf>> {
+        Ok(self.paths(key)?.into_iter().find(|path| path.metadata().is_ok_and(|meta| meta.len() > 0)))
+    }
+    pub fn is_cached(&self, key: &InputKey) -> MyResult<bool> {
+        Ok(self.stored(key)?.is_some())
+    }
+    pub fn read(&self, key: &InputKey) -> MyResult<String> {
+        if let Some(stored) = self.stored(key)? {
+            return read_path(&stored.to_string_lossy());
+        }
+        let path = self.path(key)?;
+        let examples = path.with_file_name(format!("examples{}.txt", key.day));
+        if key.variant == "demo" && examples.exists() {
+            let examples = examples::parse(&fs::read_to_string(&examples)?)?;
+            let demo = examples.into_iter().find(|example| example.name == "demo");
+            return Ok(demo.ok_or_else(|| format!("missing {}: no demo example", key))?.input);
+        }
+        let fetcher = self.fetcher.as_ref().ok_or_else(|| {
+            let hint = match key.variant.as_str() {
+                "input" => format!(" (fetch it with `rust-advent fetch --year {} --day {}`)", key.year, key.day),
+                _ => String::new(),
+            };
+            format!("missing {}: {}{}", key, path.display(), hint)
+        })?;
+        let input = fetcher.fetch(key)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+}
+
+#[test]
+fn test_read_from() {
+    assert_eq!(read_from("1abc2\n".as_bytes()).unwrap(), "1abc2\n");
+    let demo = read_from(&include_bytes!("../input/2023/demo5.txt.gz")[..]).unwrap();
+    assert_eq!(demo, examples::demo(YEAR, 5).unwrap());
+    assert_eq!(InputStore::default().read(&InputKey::new(YEAR, 5, "demo")).unwrap(), demo);
+    assert_eq!(read_from(&[0xff, 0xfe][..]).unwrap_err().to_string(), "input is not UTF-8");
+
+    // A reader that hands out one byte at a time is read the same way.
+    struct Trickle<'a>(&'a [u8]);
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+    assert_eq!(read_from(Trickle(include_bytes!("../input/2023/demo5.txt.gz"))).unwrap(), demo);
+    assert_eq!(read_from(Trickle(b"1abc2\n")).unwrap(), "1abc2\n");
+    let err = read_path("input/missing.txt").unwrap_err().to_string();
+    assert!(err.starts_with("input/missing.txt: "), "{}", err);
+
+    // Only the gzipped file is there.
+    let root = std::env::temp_dir().join(format!("rust-advent-gz-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("demo5.txt.gz"), include_bytes!("../input/2023/demo5.txt.gz")).unwrap();
+    let store = InputStore::new(vec![(YEAR, root.clone())]);
+    assert_eq!(store.read(&InputKey::new(YEAR, 5, "demo")).unwrap(), demo);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_file_names() {
+    assert_eq!(InputKey::input(YEAR, 3).file_name(), "input3.txt");
+    assert_eq!(InputKey::new(YEAR, 3, "demo").file_name(), "demo3.txt");
+    assert_eq!(InputKey::new(YEAR, 3, "test_2").file_name(), "test3_2.txt");
+    let paths = InputStore::default().paths(&InputKey::input(YEAR, 3)).unwrap();
+    assert_eq!(paths, [dir(YEAR).join("input3.txt"), dir(YEAR).join("input3.txt.gz")]);
+    let err = InputStore::default().read(&InputKey::new(2015, 1, "input")).unwrap_err();
+    assert_eq!(err.to_string(), "no inputs for year 2015");
+}
+
+#[test]
+fn test_fetch_once_then_cache() {
+    use crate::http;
+    use crate::site::Site;
+    use std::env;
+
+    let root = env::temp_dir().join(format!("rust-advent-inputs-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    // The server answers a single request, so a second fetch would fail.
+    let (url, server) = http::mock_server(vec![(200, "1abc2\n".into())]);
+    let site = Site { base_url: url, session: "s3cret".into() };
+    let store = InputStore::new(vec![(YEAR, root.clone())]).with_fetcher(site);
+    let key = InputKey::input(YEAR, 1);
+    assert_eq!(store.read(&key).unwrap(), "1abc2\n");
+    assert_eq!(store.read(&key).unwrap(), "1abc2\n");
+    assert_eq!(fs::read_to_string(root.join("input1.txt")).unwrap(), "1abc2\n");
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /2023/day/1/input "));
+    assert!(requests[0].contains("Cookie: session=s3cret"));
+
+    let err = store.read(&InputKey::new(YEAR, 1, "demo")).unwrap_err();
+    assert_eq!(err.to_string(), "only real inputs can be fetched, not 2023 day 1 demo");
+    let err = InputStore::new(vec![(YEAR, root.clone())]).read(&InputKey::input(YEAR, 2)).unwrap_err();
+    assert!(err.to_string().starts_with("missing 2023 day 2 input: "), "{}", err);
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,93 @@
+pub mod anonymise;
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod diff;
+pub mod examples;
+pub mod fuzz;
+pub mod gen;
+pub mod guesses;
+pub mod gzip;
+pub mod http;
+pub mod inputs;
+pub mod json;
+pub mod memory;
+pub mod minimise;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod site;
+pub mod solver;
+pub mod trace;
+pub mod types;
+pub mod watch;
+pub mod y2023;
+
+use diff::Reference;
+use gen::{Rng, Size};
+use repl::Explore;
+use solver::Solver;
+use types::MyResult;
+
+/// A parsed input of `day` to explore, or `None` when the day has no REPL
+/// commands.
+pub type Session = for<'a> fn(u32, &'a str) -> Option<MyResult<Box<dyn Explore + 'a>>>;
+
+/// A calendar: its solved days and what the tools know of its inputs. The
+/// tools take a day and give `None` for days they do not cover.
+pub struct Year {
+    pub year: u32,
+    pub days: &'static [&'static dyn Solver],
+    /// A random input of `day` that its solver accepts.
+    pub generate: fn(u32, &mut Rng, &Size) -> Option<String>,
+    /// An input of `day` with its numbers and letters changed.
+    pub anonymise: fn(u32, &mut Rng, &str) -> Option<String>,
+    pub session: Session,
+    pub references: &'static [Reference],
+}
+
+impl Year {
+    /// A calendar of `days` that none of the tools know about yet.
+    pub const fn new(year: u32, days: &'static [&'static dyn Solver]) -> Year {
+        fn no_generator(_: u32, _: &mut Rng, _: &Size) -> Option<String> {
+            None
+        }
+        fn no_anonymiser(_: u32, _: &mut Rng, _: &str) -> Option<String> {
+            None
+        }
+        fn no_session(_: u32, _: &str) -> Option<MyResult<Box<dyn Explore + '_>>> {
+            None
+        }
+        Year {
+            year,
+            days,
+            generate: no_generator,
+            anonymise: no_anonymiser,
+            session: no_session,
+            references: &[],
+        }
+    }
+}
+
+/// Every calendar, in year order.
+pub static YEARS: &[&Year] = &[
+    &y2023::YEAR,
+];
+
+/// The calendar of `year`.
+pub fn year(year: u32) -> MyResult<&'static Year> {
+    let calendar = YEARS.iter().find(|calendar| calendar.year == year);
+    Ok(*calendar.ok_or_else(|| format!("no calendar for {}", year))?)
+}
+
+/// The solved days of `year`, in day order.
+pub fn days(year: u32) -> MyResult<&'static [&'static dyn Solver]> {
+    Ok(self::year(year)?.days)
+}
+
+/// The solver of `day` of `year`.
+pub fn solver(year: u32, day: u32) -> MyResult<&'static dyn Solver> {
+    let solver = days(year)?.iter().find(|solver| solver.day() == day);
+    Ok(*solver.ok_or_else(|| format!("no solver for {} day {}", year, day))?)
+}
@@ -1,23 +1,606 @@
-mod types;
-mod day1;
-mod day5;
-mod day2;
-mod day3;
-mod day4;
+use rust_advent::anonymise;
+use rust_advent::answers::Answers;
+use rust_advent::bench;
+use rust_advent::cache::{self, Cache};
+use rust_advent::diff;
+use rust_advent::examples;
+use rust_advent::fuzz;
+use rust_advent::gen::{self, Size};
+use rust_advent::guesses::Guesses;
+use rust_advent::minimise::{self, Predicate};
+use rust_advent::inputs::{self, InputKey, InputStore, YEAR};
+use rust_advent::memory;
+use rust_advent::repl;
+use rust_advent::report;
+use rust_advent::runner;
+use rust_advent::scaffold;
+use rust_advent::site::{Site, Verdict};
+use rust_advent::solver::{Answer, Part, Solver};
+use rust_advent::trace::{self, Filter};
+use rust_advent::types::MyResult;
+use rust_advent::watch::{self, Watched};
+use rust_advent::YEARS;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
 
-use types::MyResult;
+#[cfg(feature = "alloc-count")]
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
 
-fn main() -> MyResult<()> {
-    println!("\n-- Run --",);
-    day1::p1("./input/input1.txt")?;
-    day1::p2("./input/input1.txt")?;
-    day2::p1("./input/input2.txt")?;
-    day2::p2("./input/input2.txt")?;
-    day3::p1("./input/input3.txt")?;
-    day3::p2("./input/input3.txt")?;
-    day4::p1("./input/input4.txt")?;
-    day4::p2("./input/input4.txt")?;
-    day5::p1("./input/input5.txt")?;
-    day5::p2("./input/demo5.txt")?;
+const USAGE: &str = "usage:
+    rust-advent [run] [--day N | --all] [--jobs N] [--no-cache]
+    rust-advent [run] --day N --input FILE|- [--no-cache]
+    rust-advent verify [--day N | --all] [--jobs N] [--no-cache]
+    rust-advent watch --day N [--interval MS]
+    rust-advent gen --day N [--seed S] [--lines N] [--width N] [--ranges N] [--winners N] [--drawn N] [--max N]
+    rust-advent diff [--day N | --all] [--seeds N] [gen sizes]
+    rust-advent fuzz [--day N | --all] [--runs N] [--seed S]
+    rust-advent minimise --day N --predicate panic|error|diff [--part 1|2] [--input FILE] [--out FILE]
+    rust-advent anonymise --day N [--predicate panic|error|diff] [--part 1|2] [--seed S] [--input FILE] [--out FILE]
+    rust-advent bench [--day N | --all] [--runs N] [--jobs N] [--threshold PERCENT] [--history FILE]
+    rust-advent report [--day N] [--runs N] [--out FILE] [--html [FILE]]
+    rust-advent repl --day N [--input FILE]
+    rust-advent new --day N
+    rust-advent fetch --day N [--base-url URL] [--session TOKEN]
+    rust-advent submit --day N --part 1|2 [--answer X] [--base-url URL] [--session TOKEN]
+built with --features alloc-count, runs also report allocations and peak heap
+any command also takes -v or -vv, and --trace TARGET[=LEVEL],... where a target is YEAR/dayN
+    or YEAR for all its days and LEVEL is info, debug or trace (e.g. --trace 2023/day5,2023/day4=debug)
+any command also takes --year Y, the calendar the days belong to, 2023 by default
+input files may be gzipped, and - reads stdin
+report's rss column needs Linux, which lets the resident high water mark be reset; elsewhere it shows -
+watch follows the input and answers files, not the sources: rebuild and restart it after a code change";
+
+/// Command line: a command followed by `--flag [value]` pairs.
+struct Args {
+    command: String,
+    flags: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> MyResult<Args> {
+        let mut args = args.by_ref().peekable();
+        let command = match args.peek() {
+            Some(arg) if !arg.starts_with('-') => args.next().unwrap(),
+            _ => "run".into(),
+        };
+        let mut flags = vec![];
+        while let Some(arg) = args.next() {
+            // `-v` and `-vv` are kept as `--verbose 1` and `--verbose 2`.
+            if let Some(v) = arg.strip_prefix('-').filter(|v| !v.is_empty() && v.chars().all(|c| c == 'v')) {
+                flags.push(("verbose".into(), Some(v.len().to_string())));
+                continue;
+            }
+            let name = arg.strip_prefix("--").ok_or_else(|| format!("unexpected '{}'\n{}", arg, USAGE))?;
+            let value = args.next_if(|value| !value.starts_with("--"));
+            flags.push((name.to_string(), value));
+        }
+        Ok(Args { command, flags })
+    }
+    fn has(&self, name: &str) -> bool {
+        self.flags.iter().any(|(flag, _)| flag == name)
+    }
+    fn value(&self, name: &str) -> Option<&str> {
+        self.flags
+            .iter()
+            .find(|(flag, _)| flag == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+    fn parsed<T: FromStr>(&self, name: &str, default: T) -> MyResult<T> {
+        match self.value(name) {
+            Some(value) => value.parse().map_err(|_| format!("bad --{} '{}'", name, value).into()),
+            None if self.has(name) => Err(format!("--{} needs a value", name).into()),
+            None => Ok(default),
+        }
+    }
+    /// The calendar picked by `--year Y`, the current one by default.
+    fn year(&self) -> MyResult<u32> {
+        self.parsed("year", YEAR)
+    }
+    /// Days of the year picked by `--day N`, or every day for `--all` or no
+    /// selection.
+    fn days(&self) -> MyResult<Vec<&'static dyn Solver>> {
+        let year = self.year()?;
+        match self.value("day") {
+            Some(day) => {
+                let day: u32 = day.parse().map_err(|_| format!("bad --day '{}'", day))?;
+                Ok(vec![rust_advent::solver(year, day)?])
+            }
+            None => Ok(rust_advent::days(year)?.to_vec()),
+        }
+    }
+}
+
+/// The answer cache, or one that is never used with `--no-cache`.
+fn cache(args: &Args) -> MyResult<Cache> {
+    match args.has("no-cache") {
+        true => Ok(Cache::disabled()),
+        false => Cache::load(Path::new(cache::PATH)),
+    }
+}
+
+fn run(args: &Args) -> MyResult<()> {
+    let days = args.days()?;
+    let workers = args.parsed("jobs", runner::default_workers())?;
+    let cache = cache(args)?;
+    let runs = match (args.has("input"), days.as_slice()) {
+        (false, _) => runner::run_days(&days, 1, workers, &cache),
+        (true, [solver]) if args.has("day") => {
+            let input = input_of(args, *solver)?;
+            vec![cache.run(*solver, &input, 1).map_err(|e| e.to_string())]
+        }
+        (true, _) => return Err("--input needs --day N".into()),
+    };
+    cache.save(Path::new(cache::PATH))?;
+    let mut failed = 0;
+    for (solver, run) in days.iter().zip(runs) {
+        let run = match run {
+            Ok(run) => run,
+            Err(e) => {
+                failed += 1;
+                println!("day{}: error: {}", solver.day(), e);
+                continue;
+            }
+        };
+        for part in Part::ALL {
+            let mut time = match run.parts[part.index()].first() {
+                Some(time) => format!("{:.1?}", time),
+                None => "cached".into(),
+            };
+            if let Some(usage) = run.part_memory[part.index()] {
+                time += &format!(", {} allocs, peak {}", usage.allocations, memory::bytes(usage.peak));
+            }
+            match &run.answers[part.index()] {
+                Ok(answer) => println!("day{}{}: {} ({})", run.day, part, answer, time),
+                Err(e) => {
+                    failed += 1;
+                    println!("day{}{}: error: {}", run.day, part, e)
+                }
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} failure(s)", failed).into()),
+    }
+}
+
+/// Checks every day against its examples and the known answers of its input.
+fn verify(args: &Args) -> MyResult<()> {
+    let days = args.days()?;
+    let workers = args.parsed("jobs", runner::default_workers())?;
+    let cache = cache(args)?;
+    let results = runner::parallel(&days, workers, |solver| {
+        let (year, day) = (solver.year(), solver.day());
+        let mut inputs = vec![];
+        for example in examples::load(year, day).map_err(|e| e.to_string())? {
+            inputs.push((example.name, example.input, example.expected));
+        }
+        let answers = Answers::load(year, day).map_err(|e| e.to_string())?;
+        if answers != Answers::default() {
+            inputs.push(("input".into(), runner::input(year, day).map_err(|e| e.to_string())?, answers));
+        }
+        inputs
+            .into_iter()
+            .map(|(name, input, expected)| {
+                let run = cache.run(*solver, &input, 1).map_err(|e| e.to_string())?;
+                Ok((name, examples::check(&run, &expected)))
+            })
+            .collect::<Result<Vec<_>, String>>()
+    });
+    cache.save(Path::new(cache::PATH))?;
+    let mut failed = 0;
+    for (solver, result) in days.iter().zip(results) {
+        match result {
+            Ok(inputs) if inputs.is_empty() => println!("day{}: nothing to verify", solver.day()),
+            Ok(inputs) => {
+                for (name, checks) in inputs {
+                    for check in checks {
+                        failed += usize::from(!check.passed());
+                        println!("day{} {}: {}", solver.day(), name, check);
+                    }
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("day{}: error: {}", solver.day(), e);
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} failure(s)", failed).into()),
+    }
+}
+
+/// Re-runs a day whenever its input, plain or gzipped, or its answers file
+/// changes, until killed. The solver is the one built into the binary, so
+/// source changes need a rebuild and a new watch.
+fn watch(args: &Args) -> MyResult<()> {
+    let solver = match args.days()?.as_slice() {
+        [solver] if args.has("day") => *solver,
+        _ => return Err("watch needs --day N".into()),
+    };
+    let interval = Duration::from_millis(args.parsed("interval", 500)?);
+    let (year, day) = (solver.year(), solver.day());
+    let [input, gz] = InputStore::default().paths(&InputKey::input(year, day))?;
+    let answers_file = Answers::path(year, day);
+    println!("watching {}, {} and {}", input.display(), gz.display(), answers_file.display());
+    let mut watched = Watched::new(vec![input, gz, answers_file]);
+    let mut previous: Option<[Result<Answer, String>; 2]> = None;
+    loop {
+        let answers = runner::input(year, day)
+            .and_then(|input| solver.run(&input, 1))
+            .map(|run| run.answers)
+            .map_err(|e| e.to_string());
+        match (&answers, Answers::load(year, day)) {
+            (Ok(answers), Ok(expected)) => {
+                for part in Part::ALL {
+                    let before = previous.as_ref().map(|previous| &previous[part.index()]);
+                    println!("{}", watch::line(day, part, &answers[part.index()], before, expected.get(part)));
+                }
+            }
+            (Err(e), _) => println!("day{}: error: {}", day, e),
+            (_, Err(e)) => println!("day{}: error: {}", day, e),
+        }
+        if let Ok(answers) = answers {
+            previous = Some(answers);
+        }
+        while watched.poll().is_empty() {
+            thread::sleep(interval);
+        }
+    }
+}
+
+/// Generator sizes from `--lines`, `--width` and so on.
+fn size(args: &Args) -> MyResult<Size> {
+    let default = Size::default();
+    Ok(Size {
+        lines: args.parsed("lines", default.lines)?,
+        width: args.parsed("width", default.width)?,
+        ranges: args.parsed("ranges", default.ranges)?,
+        winners: args.parsed("winners", default.winners)?,
+        drawn: args.parsed("drawn", default.drawn)?,
+        max: match args.has("max") {
+            true => Some(args.parsed("max", 0)?),
+            false => None,
+        },
+    })
+}
+
+/// Prints a random input for a day, the same one for the same seed and sizes.
+fn generate(args: &Args) -> MyResult<()> {
+    let day = args.parsed("day", 0)?;
+    if day == 0 {
+        return Err("gen needs --day N".into());
+    }
+    print!("{}", gen::input(args.year()?, day, args.parsed("seed", 0)?, &size(args)?)?);
     Ok(())
 }
+
+/// Checks the solvers against their references on the real input and on
+/// generated ones, and shows the smallest generated input that disagrees.
+fn differ(args: &Args) -> MyResult<()> {
+    let days: Vec<(u32, u32)> = args.days()?.iter().map(|solver| (solver.year(), solver.day())).collect();
+    let seeds = args.parsed("seeds", 100)?;
+    let size = size(args)?;
+    let mut failed = 0;
+    for reference in diff::references().filter(|reference| days.contains(&(reference.year, reference.day))) {
+        match runner::input(reference.year, reference.day) {
+            Ok(input) => {
+                if let Some(divergence) = diff::compare(reference, &input)? {
+                    failed += 1;
+                    println!("{}: differs on the input: {}", reference, divergence);
+                    continue;
+                }
+            }
+            Err(e) => println!("{}: skipping the input: {}", reference, e),
+        }
+        match diff::search(reference, 0..seeds, &size)? {
+            None => println!("{}: agrees on the input and {} generated inputs", reference, seeds),
+            Some(case) => {
+                failed += 1;
+                println!("{}: differs: {}", reference, case.divergence);
+                println!(
+                    "    rust-advent gen --year {} --day {} --seed {} {}",
+                    reference.year,
+                    reference.day,
+                    case.seed,
+                    case.size.flags()
+                );
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} failure(s)", failed).into()),
+    }
+}
+
+/// Mutates each day's corpus under fuzz/, keeping inputs that reach new
+/// outcomes and saving the ones that crash.
+fn fuzz(args: &Args) -> MyResult<()> {
+    let runs = args.parsed("runs", 10_000)?;
+    let seed = args.parsed("seed", 0)?;
+    let mut crashes = 0;
+    for solver in args.days()? {
+        let (year, day) = (solver.year(), solver.day());
+        let corpus = fuzz::load_corpus(year, day)?;
+        let report = fuzz::fuzz(solver, &corpus, seed, runs);
+        for input in &report.new_inputs {
+            fuzz::save(&fuzz::corpus_dir(year, day), "", input)?;
+        }
+        println!(
+            "day{}: {} runs, {} new inputs, {} crashes",
+            day,
+            report.runs,
+            report.new_inputs.len(),
+            report.crashes.len()
+        );
+        for (input, message) in &report.crashes {
+            let path = fuzz::save(&fuzz::crash_dir(), &format!("{}-day{}-", year, day), input)?;
+            println!("    {}: {}", path.display(), message);
+        }
+        crashes += report.crashes.len();
+    }
+    match crashes {
+        0 => Ok(()),
+        _ => Err(format!("{} crash(es)", crashes).into()),
+    }
+}
+
+/// The `--predicate` of `minimise` and `anonymise`, about `--part` (1 by
+/// default) of the day of `solver`.
+fn predicate(args: &Args, solver: &dyn Solver) -> MyResult<Option<Predicate<'static>>> {
+    let (year, day) = (solver.year(), solver.day());
+    let part = match args.value("part") {
+        None | Some("1") => Part::One,
+        Some("2") => Part::Two,
+        Some(part) => return Err(format!("bad --part '{}'", part).into()),
+    };
+    Ok(match args.value("predicate") {
+        None => None,
+        Some("panic") => Some(Predicate::Panic),
+        Some("error") => Some(Predicate::Error(part)),
+        Some("diff") => {
+            let reference =
+                diff::references().find(|reference| (reference.year, reference.day, reference.part) == (year, day, part));
+            Some(Predicate::Mismatch(reference.ok_or_else(|| format!("no reference for {} day{}{}", year, day, part))?))
+        }
+        Some(predicate) => return Err(format!("bad --predicate '{}'", predicate).into()),
+    })
+}
+
+/// The file given with `--input`, `-` for stdin, or the input of the day of
+/// `solver`.
+fn input_of(args: &Args, solver: &dyn Solver) -> MyResult<String> {
+    match args.value("input") {
+        Some(path) => inputs::read_path(path),
+        None => runner::input(solver.year(), solver.day()),
+    }
+}
+
+/// Writes `text` to `--out`, or under `dir` named by its hash.
+fn write_out(args: &Args, dir: &Path, prefix: &str, text: &str) -> MyResult<PathBuf> {
+    match args.value("out") {
+        Some(path) => {
+            fs::write(path, text)?;
+            Ok(path.into())
+        }
+        None => fuzz::save(dir, prefix, text),
+    }
+}
+
+/// Shrinks an input that panics, fails or disagrees with a reference and
+/// saves what is left.
+fn minimise(args: &Args) -> MyResult<()> {
+    let solver = match args.days()?.as_slice() {
+        [solver] if args.has("day") => *solver,
+        _ => return Err("minimise needs --day N".into()),
+    };
+    let day = solver.day();
+    let predicate = predicate(args, solver)?.ok_or("minimise needs --predicate panic|error|diff")?;
+    let input = input_of(args, solver)?;
+    let minimal = minimise::minimise(solver, &input, &predicate)?;
+    let path = write_out(args, &minimise::out_dir(), &format!("{}-day{}-", solver.year(), day), &minimal)?;
+    println!(
+        "day{}: {} lines down to {} ({} bytes), still {}: {}",
+        day,
+        input.lines().count(),
+        minimal.lines().count(),
+        minimal.len(),
+        predicate,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Rewrites an input into one that can be shared and still ends the same
+/// way, and saves it.
+fn anonymise(args: &Args) -> MyResult<()> {
+    let solver = match args.days()?.as_slice() {
+        [solver] if args.has("day") => *solver,
+        _ => return Err("anonymise needs --day N".into()),
+    };
+    let day = solver.day();
+    let predicate = predicate(args, solver)?;
+    let input = input_of(args, solver)?;
+    let (seed, anonymised) = anonymise::preserving(solver, &input, args.parsed("seed", 0)?, predicate.as_ref())?;
+    let path = write_out(args, &anonymise::out_dir(), &format!("{}-day{}-", solver.year(), day), &anonymised)?;
+    println!("day{}: anonymised with seed {}: {}", day, seed, path.display());
+    Ok(())
+}
+
+/// Questions a day's parsed input interactively; `help` lists what can be
+/// asked.
+fn explore(args: &Args) -> MyResult<()> {
+    let solver = match args.days()?.as_slice() {
+        [solver] if args.has("day") => *solver,
+        _ => return Err("repl needs --day N".into()),
+    };
+    let input = input_of(args, solver)?;
+    let session = repl::session(solver.year(), solver.day(), &input)?;
+    let prompt = format!("day{}> ", solver.day());
+    repl::run(&*session, &prompt, Path::new(repl::HISTORY_PATH))
+}
+
+fn bench(args: &Args) -> MyResult<()> {
+    let runs = args.parsed("runs", 10)?;
+    let threshold = args.parsed("threshold", 10.0)?;
+    let workers = args.parsed("jobs", 1)?;
+    let path = args.value("history").unwrap_or("bench.json");
+    let days = args.days()?;
+    let mut timings = vec![];
+    let mut failed = 0;
+    for (solver, run) in days.iter().zip(runner::run_days(&days, runs, workers, &Cache::disabled())) {
+        match run {
+            Ok(run) => timings.extend(bench::timings(&run)),
+            Err(e) => {
+                failed += 1;
+                println!("day{}: error: {}", solver.day(), e);
+            }
+        }
+    }
+    let mut history = bench::History::load(Path::new(path))?;
+    let (table, regressions) = bench::report(&timings, &history.previous(), threshold);
+    print!("{}", table);
+    history.push(runs, &timings);
+    history.save(Path::new(path))?;
+    let mut problems = vec![];
+    if failed > 0 {
+        problems.push(format!("{} failure(s)", failed));
+    }
+    if !regressions.is_empty() {
+        problems.push(format!("slower than the last run by more than {}%: {}", threshold, regressions.join(", ")));
+    }
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(problems.join("; ").into()),
+    }
+}
+
+/// Writes a table of every day's answers, their status and cost, for every
+/// year unless `--year` or `--day` picks some.
+fn report(args: &Args) -> MyResult<()> {
+    let solvers = match args.has("year") || args.has("day") {
+        true => args.days()?,
+        false => YEARS.iter().flat_map(|year| year.days.iter().copied()).collect(),
+    };
+    let rows = report::rows(&solvers, args.parsed("runs", 1)?)?;
+    let path = args.value("out").unwrap_or("report.md");
+    fs::write(path, report::markdown(&rows))?;
+    println!("wrote {}", path);
+    if args.has("html") {
+        let path = args.value("html").unwrap_or("report.html");
+        fs::write(path, report::html(&rows))?;
+        println!("wrote {}", path);
+    }
+    println!("{}", report::summary(&rows));
+    Ok(())
+}
+
+fn new(args: &Args) -> MyResult<()> {
+    let day = args.value("day").ok_or("new needs --day N")?;
+    let day = day.parse().map_err(|_| format!("bad --day '{}'", day))?;
+    if !Path::new("Cargo.toml").exists() {
+        return Err("run new from the project root".into());
+    }
+    for path in scaffold::new_day(Path::new("."), args.year()?, day)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn fetch(args: &Args) -> MyResult<()> {
+    let day = args.parsed("day", 0)?;
+    if day == 0 {
+        return Err("fetch needs --day N".into());
+    }
+    let key = InputKey::input(args.year()?, day);
+    let fetcher = Site::from_env(args.value("base-url"), args.value("session"))?;
+    let store = InputStore::default().with_fetcher(fetcher);
+    let path = store.path(&key)?;
+    if store.is_cached(&key)? {
+        println!("{} already cached in {}", key, path.display());
+    } else {
+        store.read(&key)?;
+        println!("fetched {} into {}", key, path.display());
+    }
+    Ok(())
+}
+
+/// Posts an answer, computed by the solver unless given, and records the
+/// verdict. Answers already ruled out by earlier guesses are not sent.
+fn submit(args: &Args) -> MyResult<()> {
+    let solver = match args.days()?.as_slice() {
+        [solver] if args.has("day") => *solver,
+        _ => return Err("submit needs --day N".into()),
+    };
+    let part = match args.value("part") {
+        Some("1") => Part::One,
+        Some("2") => Part::Two,
+        _ => return Err("submit needs --part 1|2".into()),
+    };
+    let (year, day) = (solver.year(), solver.day());
+    let answer = match args.value("answer") {
+        Some(answer) => answer.parse().map_err(|_| format!("bad --answer '{}'", answer))?,
+        None => {
+            let run = solver.run(&runner::input(year, day)?, 1)?;
+            run.answers[part.index()].clone()?
+        }
+    };
+    let path = Guesses::path(year, day);
+    let mut guesses = Guesses::load(&path)?;
+    if let Some(reason) = guesses.refusal(part, answer) {
+        return Err(format!("not submitting day{}{}: {}", day, part, reason).into());
+    }
+    let site = Site::from_env(args.value("base-url"), args.value("session"))?;
+    let verdict = site.submit(year, day, part, answer)?;
+    guesses.record(part, answer, verdict);
+    guesses.save(&path)?;
+    println!("day{}{}: {} is {}", day, part, answer, verdict);
+    if verdict == Verdict::Correct {
+        let mut answers = Answers::load(year, day)?;
+        if answers.get(part).is_none() {
+            match part {
+                Part::One => answers.p1 = Some(answer),
+                Part::Two => answers.p2 = Some(answer),
+            }
+            fs::write(Answers::path(year, day), answers.to_string())?;
+            println!("recorded in {}", Answers::path(year, day).display());
+        }
+    }
+    Ok(())
+}
+
+fn main() -> MyResult<()> {
+    let args = Args::parse(env::args().skip(1))?;
+    let mut filter = Filter::verbosity(args.parsed("verbose", 0)?);
+    if let Some(spec) = args.value("trace") {
+        let spec: Filter = spec.parse()?;
+        filter.default = filter.default.max(spec.default);
+        filter.targets.extend(spec.targets);
+    }
+    trace::set(filter);
+    match args.command.as_str() {
+        "run" => run(&args),
+        "verify" => verify(&args),
+        "watch" => watch(&args),
+        "gen" => generate(&args),
+        "diff" => differ(&args),
+        "fuzz" => fuzz(&args),
+        "minimise" => minimise(&args),
+        "anonymise" => anonymise(&args),
+        "bench" => bench(&args),
+        "report" => report(&args),
+        "repl" => explore(&args),
+        "new" => new(&args),
+        "fetch" => fetch(&args),
+        "submit" => submit(&args),
+        command => Err(format!("unknown command '{}'\n{}", command, USAGE).into()),
+    }
+}
@@ -0,0 +1,110 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Heap use of one step, counted on the thread that ran it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Usage {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Most bytes live at once above what was live when the step started.
+    pub peak: u64,
+}
+
+/// The system allocator, counting what each thread allocates. The binary
+/// installs it with the `alloc-count` feature:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: memory::Counting = memory::Counting;
+/// ```
+pub struct Counting;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    /// Bytes allocated minus bytes freed on this thread, which goes below
+    /// zero when it frees what another thread allocated.
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+fn add(size: usize) {
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size as u64));
+    grow(size as i64);
+}
+
+fn grow(by: i64) {
+    let Ok(live) = LIVE.try_with(|live| {
+        live.set(live.get() + by);
+        live.get()
+    }) else {
+        return;
+    };
+    let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live)));
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        add(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        add(layout.size());
+        unsafe { System.alloc_zeroed(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        grow(-(layout.size() as i64));
+        unsafe { System.dealloc(ptr, layout) }
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + new_size.saturating_sub(layout.size()) as u64));
+        grow(new_size as i64 - layout.size() as i64);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Whether [`Counting`] is the global allocator, which is only known once
+/// something has been allocated.
+pub fn counting() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` and counts what it allocates on this thread, if the counting
+/// allocator is installed. Measures can be nested.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    let allocations = ALLOCATIONS.with(Cell::get);
+    let bytes = BYTES.with(Cell::get);
+    let live = LIVE.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(live));
+    let result = f();
+    let peak = PEAK.with(|peak| peak.replace(peak.get().max(outer_peak)));
+    let usage = Usage {
+        allocations: ALLOCATIONS.with(Cell::get) - allocations,
+        bytes: BYTES.with(Cell::get) - bytes,
+        peak: (peak - live).max(0) as u64,
+    };
+    (result, counting().then_some(usage))
+}
+
+/// `n` bytes in B, KiB or MiB.
+pub fn bytes(n: u64) -> String {
+    match n {
+        n if n >= 1 << 20 => format!("{:.1} MiB", n as f64 / (1 << 20) as f64),
+        n if n >= 1 << 10 => format!("{:.1} KiB", n as f64 / (1 << 10) as f64),
+        n => format!("{} B", n),
+    }
+}
+
+#[test]
+fn test_bytes() {
+    assert_eq!(bytes(0), "0 B");
+    assert_eq!(bytes(1536), "1.5 KiB");
+    assert_eq!(bytes(3 << 20), "3.0 MiB");
+}
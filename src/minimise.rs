@@ -0,0 +1,180 @@
+use crate::diff::{self, Reference};
+use crate::fuzz;
+use crate::solver::{Part, Solver};
+use crate::types::MyResult;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where the `minimise` command writes its reproducers.
+pub fn out_dir() -> PathBuf {
+    PathBuf::from("fuzz/minimised")
+}
+
+/// What makes an input worth keeping while it is being shrunk.
+pub enum Predicate<'a> {
+    /// Solving panics.
+    Panic,
+    /// The input parses but the part fails.
+    Error(Part),
+    /// The input parses and the solver disagrees with the reference.
+    Mismatch(&'a Reference),
+}
+
+impl fmt::Display for Predicate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Predicate::Panic => write!(f, "panics"),
+            Predicate::Error(part) => write!(f, "{} fails", part),
+            Predicate::Mismatch(reference) => write!(f, "differs from {}", reference),
+        }
+    }
+}
+
+impl Predicate<'_> {
+    pub fn holds(&self, solver: &dyn Solver, input: &str) -> bool {
+        match self {
+            Predicate::Panic => fuzz::panics(solver, input),
+            Predicate::Error(part) => solver
+                .run(input, 1)
+                .is_ok_and(|run| run.answers[part.index()].is_err()),
+            Predicate::Mismatch(reference) => {
+                solver.run(input, 1).is_ok() && diff::compare(reference, input).is_ok_and(|found| found.is_some())
+            }
+        }
+    }
+}
+
+/// Delta debugging: drops ever smaller chunks of `units` for as long as
+/// `test` still holds on what is left.
+pub fn ddmin(mut units: Vec<String>, mut test: impl FnMut(&[String]) -> bool) -> Vec<String> {
+    let mut chunks = 2;
+    while units.len() >= 2 {
+        let size = units.len().div_ceil(chunks);
+        let smaller = (0..units.len()).step_by(size).find_map(|start| {
+            let end = (start + size).min(units.len());
+            let rest = [&units[..start], &units[end..]].concat();
+            test(&rest).then_some(rest)
+        });
+        match smaller {
+            Some(rest) => {
+                units = rest;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunks >= units.len() => break,
+            None => chunks = (chunks * 2).min(units.len()),
+        }
+    }
+    // A single unit left may not be needed either.
+    if units.len() == 1 && test(&[]) {
+        units.clear();
+    }
+    units
+}
+
+/// Runs of digits, runs of letters, and every other character on its own.
+fn tokens(line: &str) -> Vec<String> {
+    let class = |c: char| match c {
+        c if c.is_ascii_digit() => 0,
+        c if c.is_alphabetic() => 1,
+        _ => 2,
+    };
+    let mut tokens: Vec<String> = vec![];
+    let mut last = None;
+    for c in line.chars() {
+        match tokens.last_mut() {
+            Some(token) if class(c) < 2 && last == Some(class(c)) => token.push(c),
+            _ => tokens.push(c.to_string()),
+        }
+        last = Some(class(c));
+    }
+    tokens
+}
+
+/// The smallest input found that still satisfies `predicate`, removing whole
+/// lines first and then tokens within each line.
+pub fn minimise(solver: &dyn Solver, input: &str, predicate: &Predicate) -> MyResult<String> {
+    if !predicate.holds(solver, input) {
+        return Err(format!("the input does not start out as one that {}", predicate).into());
+    }
+    Ok(fuzz::quietly(|| shrink(solver, input, predicate)))
+}
+
+fn shrink(solver: &dyn Solver, input: &str, predicate: &Predicate) -> String {
+    let lines = input.split_inclusive('\n').map(String::from).collect();
+    let mut lines = ddmin(lines, |lines| predicate.holds(solver, &lines.concat()));
+    for i in 0..lines.len() {
+        let (body, end) = match lines[i].strip_suffix('\n') {
+            Some(body) => (body.to_string(), "\n"),
+            None => (lines[i].clone(), ""),
+        };
+        let kept = ddmin(tokens(&body), |tokens| {
+            let mut candidate = lines.clone();
+            candidate[i] = tokens.concat() + end;
+            predicate.holds(solver, &candidate.concat())
+        });
+        lines[i] = kept.concat() + end;
+    }
+    lines.concat()
+}
+
+#[test]
+fn test_ddmin() {
+    let units: Vec<String> = (0..40).map(|i| i.to_string()).collect();
+    let kept = ddmin(units, |units| units.contains(&"3".into()) && units.contains(&"27".into()));
+    assert_eq!(kept, ["3", "27"]);
+    assert_eq!(tokens("Card 12: 4|x"), ["Card", " ", "12", ":", " ", "4", "|", "x"]);
+}
+
+#[test]
+fn test_panic_predicate() {
+    use crate::solver::{Answer, Day};
+
+    /// Prints nothing back, so it never round trips, and panics on `boom`.
+    struct Lossy;
+    impl Day for Lossy {
+        const YEAR: u32 = 2023;
+        const DAY: u32 = 1;
+        type Model<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> MyResult<Vec<&str>> {
+            Ok(input.lines().collect())
+        }
+        fn print(_: &Vec<&str>) -> String {
+            String::new()
+        }
+        fn p1(lines: &Vec<&str>) -> MyResult<Answer> {
+            Ok(lines.len() as Answer)
+        }
+        fn p2(lines: &Vec<&str>) -> MyResult<Answer> {
+            assert!(!lines.contains(&"boom"));
+            Err("no part two".into())
+        }
+    }
+
+    fuzz::quietly(|| {
+        assert!(fuzz::execute(&Lossy, "a\nb\n").is_err());
+        assert!(!Predicate::Panic.holds(&Lossy, "a\nb\n"));
+        assert!(Predicate::Panic.holds(&Lossy, "a\nboom\n"));
+        assert_eq!(minimise(&Lossy, "a\nboom\nc\n", &Predicate::Panic).unwrap(), "boom\n");
+    });
+}
+
+#[test]
+fn test_minimise_mismatch() {
+    use crate::solver::Answer;
+    use crate::y2023::day4;
+
+    fn wrong(input: &str) -> MyResult<Answer> {
+        Ok(day4::points_naive(input)? + u64::from(input.lines().count() > 3))
+    }
+    let reference = Reference { year: 2023, day: 4, part: Part::One, name: "wrong past three cards", solve: wrong };
+    let input = std::fs::read_to_string("input/2023/input4.txt").unwrap();
+    let minimal = minimise(&day4::Day4, &input, &Predicate::Mismatch(&reference)).unwrap();
+    let lines: Vec<&str> = minimal.lines().collect();
+    assert_eq!(lines.len(), 4, "{}", minimal);
+    for line in lines {
+        let id = line.strip_prefix("Card").and_then(|line| line.strip_suffix(":|"));
+        assert!(id.is_some_and(|id| id.parse::<u32>().is_ok()), "{}", minimal);
+    }
+    assert!(minimise(&day4::Day4, "Card 1: 1 | 2\n", &Predicate::Mismatch(&reference)).is_err());
+}
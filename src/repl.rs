@@ -0,0 +1,308 @@
+use crate::solver::Day;
+use crate::types::MyResult;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::Path;
+use std::process::{Command as Process, Stdio};
+
+pub const HISTORY_PATH: &str = ".rust-advent-history";
+/// Lines of history kept between sessions.
+const HISTORY_LEN: usize = 500;
+
+/// A command of the REPL: its name, what it takes and what it does.
+pub struct Command {
+    pub name: &'static str,
+    pub args: &'static str,
+    pub help: &'static str,
+}
+
+pub const fn command(name: &'static str, args: &'static str, help: &'static str) -> Command {
+    Command { name, args, help }
+}
+
+/// Commands every session has, whatever the day.
+const COMMON: &[Command] = &[
+    command("help", "", "lists the commands"),
+    command("history", "", "lists the lines typed so far"),
+    command("quit", "", "ends the session"),
+    command("print", "", "prints the parsed model"),
+    command("p1", "", "solves part one"),
+    command("p2", "", "solves part two"),
+];
+
+/// A parsed input to ask questions about.
+pub trait Explore {
+    /// The commands of the day, after the common ones.
+    fn commands(&self) -> &'static [Command];
+    fn eval(&self, command: &str, args: &[&str]) -> MyResult<String>;
+}
+
+pub type Eval<M> = fn(&M, &str, &[&str]) -> MyResult<String>;
+
+/// The model of day `D` with the commands that question it.
+struct Explorer<'a, D: Day> {
+    model: D::Model<'a>,
+    commands: &'static [Command],
+    eval: Eval<D::Model<'a>>,
+}
+
+impl<'a, D: Day> Explore for Explorer<'a, D> {
+    fn commands(&self) -> &'static [Command] {
+        self.commands
+    }
+    fn eval(&self, command: &str, args: &[&str]) -> MyResult<String> {
+        match command {
+            "print" => Ok(D::print(&self.model).trim_end().to_string()),
+            "p1" => Ok(D::p1(&self.model)?.to_string()),
+            "p2" => Ok(D::p2(&self.model)?.to_string()),
+            command => (self.eval)(&self.model, command, args),
+        }
+    }
+}
+
+pub fn explorer<'a, D: Day + 'a>(
+    input: &'a str,
+    commands: &'static [Command],
+    eval: Eval<D::Model<'a>>,
+) -> MyResult<Box<dyn Explore + 'a>> {
+    Ok(Box::new(Explorer::<D> { model: D::parse(input)?, commands, eval }))
+}
+
+/// Parses `input` of `day` of `year` for a session.
+pub fn session(year: u32, day: u32, input: &str) -> MyResult<Box<dyn Explore + '_>> {
+    let session = crate::year(year)?.session;
+    session(day, input).unwrap_or_else(|| Err(format!("no REPL commands for {} day {}", year, day).into()))
+}
+
+pub fn unknown(command: &str) -> MyResult<String> {
+    Err(format!("unknown command '{}', try help", command).into())
+}
+
+/// The argument at `i`, parsed.
+pub fn arg<T: std::str::FromStr>(args: &[&str], i: usize, name: &str) -> MyResult<T> {
+    let value = args.get(i).ok_or_else(|| format!("missing {}", name))?;
+    value.parse().map_err(|_| format!("bad {} '{}'", name, value).into())
+}
+
+/// The item numbered `n` from one, such as a line.
+pub fn nth<'a, T>(items: &'a [T], n: usize, what: &str) -> MyResult<&'a T> {
+    n.checked_sub(1)
+        .and_then(|i| items.get(i))
+        .ok_or_else(|| format!("no {} {}, there are {}", what, n, items.len()).into())
+}
+
+/// What a line typed at the prompt comes to.
+#[derive(Debug, PartialEq)]
+pub enum Reply {
+    Text(String),
+    Quit,
+}
+
+/// Answers one line of input; `history` holds the lines typed before it.
+pub fn respond(explorer: &dyn Explore, history: &[String], line: &str) -> Reply {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((command, args)) = words.split_first() else {
+        return Reply::Text(String::new());
+    };
+    let text = match *command {
+        "quit" | "exit" => return Reply::Quit,
+        "help" => COMMON
+            .iter()
+            .chain(explorer.commands())
+            .map(|command| format!("{:<28} {}", format!("{} {}", command.name, command.args), command.help))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "history" => history.iter().enumerate().map(|(i, line)| format!("{:>4}  {}", i + 1, line)).collect::<Vec<_>>().join("\n"),
+        command => explorer.eval(command, args).unwrap_or_else(|e| format!("error: {}", e)),
+    };
+    Reply::Text(text)
+}
+
+/// Names of the commands `word` may be the start of.
+pub fn completions<'a>(explorer: &'a dyn Explore, word: &str) -> Vec<&'a str> {
+    COMMON
+        .iter()
+        .chain(explorer.commands())
+        .map(|command| command.name)
+        .filter(|name| name.starts_with(word))
+        .collect()
+}
+
+/// The longest text all of `names` start with.
+fn common_prefix<'a>(names: &[&'a str]) -> &'a str {
+    let Some(first) = names.first() else { return "" };
+    let len = names.iter().fold(first.len(), |len, name| {
+        first.bytes().zip(name.bytes()).take(len).take_while(|(a, b)| a == b).count()
+    });
+    &first[..len]
+}
+
+/// Puts the terminal in character mode for the life of the value, so that
+/// tab and the arrow keys reach the REPL, using `stty` since std has no
+/// terminal control.
+struct Raw {
+    saved: String,
+}
+
+impl Raw {
+    fn enter() -> Option<Raw> {
+        if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            return None;
+        }
+        let saved = Process::new("stty").arg("-g").stdin(Stdio::inherit()).output().ok()?;
+        let saved = String::from_utf8(saved.stdout).ok()?.trim().to_string();
+        let raw = Process::new("stty").args(["-icanon", "-echo", "-isig", "min", "1"]).status().ok()?;
+        raw.success().then_some(Raw { saved })
+    }
+}
+
+impl Drop for Raw {
+    fn drop(&mut self) {
+        let _ = Process::new("stty").arg(&self.saved).status();
+    }
+}
+
+/// Lines typed so far, oldest first, kept in a file between sessions.
+struct History {
+    lines: Vec<String>,
+}
+
+impl History {
+    fn load(path: &Path) -> History {
+        let text = fs::read_to_string(path).unwrap_or_default();
+        History { lines: text.lines().map(String::from).collect() }
+    }
+    fn push(&mut self, line: &str) {
+        if !line.trim().is_empty() && self.lines.last().is_none_or(|last| last != line) {
+            self.lines.push(line.to_string());
+        }
+    }
+    fn save(&self, path: &Path) -> MyResult<()> {
+        let start = self.lines.len().saturating_sub(HISTORY_LEN);
+        fs::write(path, self.lines[start..].iter().map(|line| format!("{}\n", line)).collect::<String>())?;
+        Ok(())
+    }
+}
+
+fn redraw(prompt: &str, line: &str) -> io::Result<()> {
+    print!("\r\x1b[K{}{}", prompt, line);
+    io::stdout().flush()
+}
+
+/// Reads a line in character mode: tab completes the command name, up and
+/// down walk the history, ctrl-c clears the line and ctrl-d on an empty line
+/// ends the session.
+fn read_raw(prompt: &str, explorer: &dyn Explore, history: &[String]) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    let mut back = history.len();
+    let mut stdin = io::stdin().lock();
+    let mut byte = || -> io::Result<Option<u8>> {
+        let mut buf = [0u8];
+        Ok((stdin.read(&mut buf)? == 1).then_some(buf[0]))
+    };
+    redraw(prompt, &line)?;
+    loop {
+        let Some(b) = byte()? else { return Ok(None) };
+        match b {
+            b'\r' | b'\n' => {
+                println!();
+                return Ok(Some(line));
+            }
+            4 if line.is_empty() => {
+                println!();
+                return Ok(None);
+            }
+            3 => line.clear(),
+            8 | 127 => {
+                line.pop();
+            }
+            b'\t' if !line.contains(' ') => {
+                let names = completions(explorer, &line);
+                match names.as_slice() {
+                    [name] => line = format!("{} ", name),
+                    [] => {}
+                    names if common_prefix(names).len() > line.len() => line = common_prefix(names).to_string(),
+                    names => println!("\n{}", names.join("  ")),
+                }
+            }
+            27 => match (byte()?, byte()?) {
+                (Some(b'['), Some(b'A')) if back > 0 => {
+                    back -= 1;
+                    line = history[back].clone();
+                }
+                (Some(b'['), Some(b'B')) if back < history.len() => {
+                    back += 1;
+                    line = history.get(back).cloned().unwrap_or_default();
+                }
+                _ => {}
+            },
+            b if (32..127).contains(&b) => line.push(char::from(b)),
+            _ => {}
+        }
+        redraw(prompt, &line)?;
+    }
+}
+
+/// Runs a session on `explorer` until quit or the end of stdin, keeping the
+/// history in `history_path`.
+pub fn run(explorer: &dyn Explore, prompt: &str, history_path: &Path) -> MyResult<()> {
+    let mut history = History::load(history_path);
+    let raw = Raw::enter();
+    loop {
+        let line = match raw {
+            Some(_) => read_raw(prompt, explorer, &history.lines)?,
+            None => {
+                let mut line = String::new();
+                (io::stdin().lock().read_line(&mut line)? > 0).then(|| line.trim_end().to_string())
+            }
+        };
+        let Some(line) = line else { break };
+        let reply = respond(explorer, &history.lines, &line);
+        history.push(&line);
+        match reply {
+            Reply::Quit => break,
+            Reply::Text(text) if text.is_empty() => {}
+            Reply::Text(text) => println!("{}", text),
+        }
+    }
+    drop(raw);
+    history.save(history_path)
+}
+
+#[test]
+fn test_respond() {
+    let demo = |day: u32| crate::examples::demo(2023, day).unwrap();
+    let ask = |explorer: &dyn Explore, line: &str| match respond(explorer, &[], line) {
+        Reply::Text(text) => text,
+        Reply::Quit => "quit".into(),
+    };
+
+    let input = demo(5);
+    let almanac = session(2023, 5, &input).unwrap();
+    assert_eq!(ask(&*almanac, "tr 79"), "82");
+    assert_eq!(
+        ask(&*almanac, "trace 79"),
+        "seed 79 -> soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 -> humidity 78 -> location 82"
+    );
+    assert!(ask(&*almanac, "maps").starts_with("seed-to-soil: 2 translations\n"));
+    assert!(ask(&*almanac, "split 79 14").contains("(split)"));
+    assert_eq!(ask(&*almanac, "seeds"), "79 14 55 13");
+    assert_eq!(ask(&*almanac, "p1"), "35");
+    assert_eq!(ask(&*almanac, "tr"), "error: missing seed");
+    assert_eq!(ask(&*almanac, "nope"), "error: unknown command 'nope', try help");
+    assert_eq!(ask(&*almanac, "quit"), "quit");
+    assert!(ask(&*almanac, "help").contains("trace SEED"));
+
+    let input = demo(4);
+    let cards = session(2023, 4, &input).unwrap();
+    assert_eq!(ask(&*cards, "card 1 matches"), "4");
+    assert_eq!(ask(&*cards, "card 1 points"), "8");
+    let input = demo(2);
+    assert!(ask(&*session(2023, 2, &input).unwrap(), "game 3").contains("possible: false"));
+    assert!(session(2023, 9, "").is_err());
+
+    assert_eq!(completions(&*cards, "p"), ["print", "p1", "p2"]);
+    assert_eq!(completions(&*almanac, "tr"), ["tr", "trace"]);
+    assert_eq!(common_prefix(&["print", "p1", "p2"]), "p");
+    assert_eq!(common_prefix(&["history", "help"]), "h");
+}
@@ -0,0 +1,234 @@
+use crate::answers::Answers;
+use crate::bench::Stats;
+use crate::memory;
+use crate::runner;
+use crate::solver::{Answer, Part, Solver};
+use crate::types::MyResult;
+use std::fmt::Write;
+use std::fs;
+use std::time::Duration;
+
+/// One part of one day as it stands: its answer, whether that is the known
+/// one, and what solving it took.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub answer: Result<Answer, String>,
+    pub expected: Option<Answer>,
+    pub parse: Option<Duration>,
+    pub solve: Option<Duration>,
+    /// Peak heap of the part, when the counting allocator is installed.
+    pub heap: Option<u64>,
+    /// How far the resident memory of the process grew while parsing and
+    /// solving the whole day, the same for both parts.
+    pub rss: Option<u64>,
+}
+
+impl Row {
+    pub fn verified(&self) -> bool {
+        self.expected.is_some_and(|expected| self.answer == Ok(expected))
+    }
+    fn status(&self) -> String {
+        match self.expected {
+            None => "unverified".into(),
+            Some(_) if self.verified() => "verified".into(),
+            Some(expected) => format!("wrong, expected {}", expected),
+        }
+    }
+    fn cells(&self) -> [String; 8] {
+        let time = |time: Option<Duration>| time.map_or("-".into(), |time| format!("{:.1?}", time));
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            match &self.answer {
+                Ok(answer) => answer.to_string(),
+                Err(e) => format!("error: {}", e),
+            },
+            self.status(),
+            time(self.parse),
+            time(self.solve),
+            self.heap.map_or("-".into(), memory::bytes),
+            self.rss.map_or("-".into(), memory::bytes),
+        ]
+    }
+}
+
+const HEADERS: [&str; 8] = ["day", "part", "answer", "status", "parse", "solve", "part heap", "day rss"];
+
+/// A `kB` field of `/proc/self/status`, in bytes.
+fn status_bytes(field: &str) -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|line| line.strip_prefix(field))?;
+    Some(line.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()? * 1024)
+}
+
+/// Runs `f` and measures how far the resident memory of the process rose
+/// above where it started. The high water mark is reset first, which only
+/// Linux allows, so elsewhere there is no measure.
+pub fn peak_memory<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+    let reset = fs::write("/proc/self/clear_refs", "5").is_ok();
+    let before = status_bytes("VmRSS:");
+    let result = f();
+    let peak = status_bytes("VmHWM:");
+    match (reset, before, peak) {
+        (true, Some(before), Some(peak)) => (result, Some(peak.saturating_sub(before))),
+        _ => (result, None),
+    }
+}
+
+/// Solves each day on its input `runs` times, one day after another so that
+/// the memory of one does not count towards another, and checks the answers
+/// against the answers files. Timings are medians. A day that fails to solve,
+/// or whose answers file does not parse, shows the error in its rows.
+pub fn rows(solvers: &[&dyn Solver], runs: usize) -> MyResult<Vec<Row>> {
+    let results = runner::parallel(solvers, 1, |solver| {
+        let input = runner::input(solver.year(), solver.day()).map_err(|e| e.to_string())?;
+        let (run, rss) = peak_memory(|| solver.run(&input, runs));
+        Ok((run.map_err(|e| e.to_string())?, rss))
+    });
+    let mut rows = vec![];
+    for (solver, result) in solvers.iter().zip(results) {
+        let expected = Answers::load(solver.year(), solver.day()).map_err(|e| e.to_string());
+        for part in Part::ALL {
+            let median = |samples: &[Duration]| (!samples.is_empty()).then(|| Stats::of(samples).median);
+            let mut row = Row {
+                year: solver.year(),
+                day: solver.day(),
+                part,
+                answer: Err(String::new()),
+                expected: expected.as_ref().ok().and_then(|expected| expected.get(part)),
+                parse: None,
+                solve: None,
+                heap: None,
+                rss: None,
+            };
+            match (&result, &expected) {
+                (_, Err(e)) => row.answer = Err(e.clone()),
+                (Ok((run, rss)), _) => {
+                    row.answer = run.answers[part.index()].clone();
+                    row.parse = median(&run.parse);
+                    row.solve = median(&run.parts[part.index()]);
+                    row.heap = run.part_memory[part.index()].map(|usage| usage.peak);
+                    row.rss = *rss;
+                }
+                (Err(e), _) => row.answer = Err(e.clone()),
+            }
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+/// How many of the parts have their known answer.
+pub fn summary<'a>(rows: impl IntoIterator<Item = &'a Row>) -> String {
+    let (mut verified, mut total) = (0, 0);
+    for row in rows {
+        verified += usize::from(row.verified());
+        total += 1;
+    }
+    format!("{} of {} parts verified", verified, total)
+}
+
+/// The rows of each year in turn, in the order they come.
+fn years(rows: &[Row]) -> Vec<(u32, Vec<&Row>)> {
+    let mut years: Vec<(u32, Vec<&Row>)> = vec![];
+    for row in rows {
+        match years.last_mut() {
+            Some((year, rows)) if *year == row.year => rows.push(row),
+            _ => years.push((row.year, vec![row])),
+        }
+    }
+    years
+}
+
+/// A Markdown table of the rows for each year.
+pub fn markdown(rows: &[Row]) -> String {
+    let mut text = String::from("# Advent of Code\n");
+    for (year, rows) in years(rows) {
+        writeln!(text, "\n## {}\n", year).unwrap();
+        writeln!(text, "| {} |", HEADERS.join(" | ")).unwrap();
+        writeln!(text, "|---:|:---:|---:|---|---:|---:|---:|---:|").unwrap();
+        for row in &rows {
+            let cells = row.cells().map(|cell| cell.replace('|', "\\|"));
+            writeln!(text, "| {} |", cells.join(" | ")).unwrap();
+        }
+        writeln!(text, "\n{}", summary(rows)).unwrap();
+    }
+    text
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: right; }
+td.verified { color: #080; }
+td.wrong { color: #c00; }
+td.unverified { color: #888; }";
+
+/// The same tables as [`markdown`] in a page that needs nothing else.
+pub fn html(rows: &[Row]) -> String {
+    let mut text = String::new();
+    writeln!(text, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">").unwrap();
+    writeln!(text, "<title>Advent of Code</title>\n<style>\n{}\n</style>\n</head>\n<body>", STYLE).unwrap();
+    writeln!(text, "<h1>Advent of Code</h1>").unwrap();
+    for (year, rows) in years(rows) {
+        writeln!(text, "<h2>{}</h2>\n<table>", year).unwrap();
+        let headers: Vec<String> = HEADERS.iter().map(|header| format!("<th>{}</th>", header)).collect();
+        writeln!(text, "<tr>{}</tr>", headers.concat()).unwrap();
+        for row in &rows {
+            let class = row.status().split(',').next().unwrap_or_default().to_string();
+            let cells: Vec<String> = row
+                .cells()
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| match i {
+                    3 => format!("<td class=\"{}\">{}</td>", class, escape(cell)),
+                    _ => format!("<td>{}</td>", escape(cell)),
+                })
+                .collect();
+            writeln!(text, "<tr>{}</tr>", cells.concat()).unwrap();
+        }
+        writeln!(text, "</table>\n<p>{}</p>", summary(rows)).unwrap();
+    }
+    writeln!(text, "</body>\n</html>").unwrap();
+    text
+}
+
+#[test]
+fn test_markdown_and_html() {
+    let ms = |ms| Some(Duration::from_millis(ms));
+    let row = |year, day, part, answer, expected| Row {
+        year,
+        day,
+        part,
+        answer,
+        expected,
+        parse: ms(2),
+        solve: ms(10),
+        heap: Some(3 << 20),
+        rss: None,
+    };
+    let rows = [
+        row(2022, 1, Part::One, Ok(24000), Some(24000)),
+        row(2023, 4, Part::One, Ok(13), Some(12)),
+        row(2023, 4, Part::Two, Err("a <b> | c".into()), None),
+    ];
+    let markdown = markdown(&rows);
+    assert!(markdown.contains("\n## 2022\n\n| day | part |"), "{}", markdown);
+    assert!(markdown.contains("| solve | part heap | day rss |\n"), "{}", markdown);
+    assert!(markdown.contains("| 1 | p1 | 24000 | verified | 2.0ms | 10.0ms | 3.0 MiB | - |\n\n1 of 1 parts verified\n"));
+    assert!(markdown.contains("| 4 | p1 | 13 | wrong, expected 12 |"), "{}", markdown);
+    assert!(markdown.contains("| 4 | p2 | error: a <b> \\| c | unverified |"), "{}", markdown);
+    assert!(markdown.ends_with("0 of 2 parts verified\n"));
+
+    let html = html(&rows);
+    assert!(html.contains("<td>error: a &lt;b&gt; | c</td><td class=\"unverified\">unverified</td>"), "{}", html);
+    assert!(html.contains("<td class=\"wrong\">wrong, expected 12</td>"));
+    assert_eq!(html.matches("<table>").count(), 2);
+    assert_eq!(summary(&rows), "1 of 3 parts verified");
+}
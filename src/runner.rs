@@ -0,0 +1,79 @@
+use crate::cache::Cache;
+use crate::inputs::{InputKey, InputStore};
+use crate::solver::{Run, Solver};
+use crate::types::MyResult;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+pub fn input(year: u32, day: u32) -> MyResult<String> {
+    InputStore::default().read(&InputKey::input(year, day))
+}
+
+/// Number of workers to use when none is asked for.
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
+}
+
+/// Runs `f` over `jobs` on up to `workers` threads and returns the results in
+/// job order. A job that panics yields an error instead of taking down the rest.
+pub fn parallel<T: Sync, R: Send>(
+    jobs: &[T],
+    workers: usize,
+    f: impl Fn(&T) -> Result<R, String> + Sync,
+) -> Vec<Result<R, String>> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<R, String>>>> =
+        Mutex::new((0..jobs.len()).map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i) else { break };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| f(job)))
+                    .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(payload))));
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|| Err("not run".into())))
+        .collect()
+}
+
+/// Runs each solver on its input `runs` times, spread over `workers` threads,
+/// unless `cache` already has its answers.
+pub fn run_days(solvers: &[&dyn Solver], runs: usize, workers: usize, cache: &Cache) -> Vec<Result<Run, String>> {
+    parallel(solvers, workers, |solver| {
+        let input = input(solver.year(), solver.day()).map_err(|e| e.to_string())?;
+        cache.run(*solver, &input, runs).map_err(|e| e.to_string())
+    })
+}
+
+#[test]
+fn test_parallel_keeps_order_and_failures() {
+    let jobs: Vec<u32> = (0..20).collect();
+    let results = parallel(&jobs, 4, |job| match job {
+        3 => Err("three".into()),
+        7 => panic!("seven"),
+        job => Ok(job * 2),
+    });
+    assert_eq!(results.len(), 20);
+    assert_eq!(results[2], Ok(4));
+    assert_eq!(results[3], Err("three".into()));
+    assert_eq!(results[7], Err("panicked: seven".into()));
+    assert_eq!(results[19], Ok(38));
+}
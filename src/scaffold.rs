@@ -0,0 +1,227 @@
+use crate::answers::Answers;
+use crate::examples;
+use crate::inputs;
+use crate::types::MyResult;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = "use crate::solver::{print_lines, Answer, Day};
+use crate::types::MyResult;
+
+pub struct Day{N};
+
+impl Day for Day{N} {
+    const YEAR: u32 = {Y};
+    const DAY: u32 = {N};
+    type Model<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> MyResult<Vec<&str>> {
+        Ok(input.lines().collect())
+    }
+
+    fn print(lines: &Vec<&str>) -> String {
+        print_lines(lines)
+    }
+
+    fn p1(_lines: &Vec<&str>) -> MyResult<Answer> {
+        Err(\"day{N}p1 not solved yet\".into())
+    }
+
+    fn p2(_lines: &Vec<&str>) -> MyResult<Answer> {
+        Err(\"day{N}p2 not solved yet\".into())
+    }
+}
+";
+
+const EXAMPLES: &str = "=== demo
+p1:
+p2:
+---
+";
+
+const YEAR_TEMPLATE: &str = "
+use crate::solver::Solver;
+use crate::Year;
+
+/// Every solved day of {Y}, in day order.
+pub static DAYS: &[&dyn Solver] = &[
+];
+
+pub static YEAR: Year = Year::new({Y}, DAYS);
+";
+
+/// Adds `pub mod dayN;` and the `DayN` solver to the text of a year's
+/// `mod.rs`, keeping both lists in day order.
+pub fn register(lib: &str, day: u32) -> MyResult<String> {
+    let module = format!("pub mod day{};", day);
+    let solver = format!("    &day{}::Day{},", day, day);
+    if lib.lines().any(|line| line == module) {
+        return Err(format!("day{} is already registered", day).into());
+    }
+    let day_of = |line: &str, prefix: &str| -> Option<u32> {
+        line.strip_prefix(prefix)?
+            .split(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok()
+    };
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+    // A year without days yet gets its first module at the top.
+    let module_at = lines
+        .iter()
+        .position(|line| day_of(line, "pub mod day").is_some_and(|d| d > day))
+        .or_else(|| lines.iter().rposition(|line| day_of(line, "pub mod day").is_some()).map(|i| i + 1))
+        .unwrap_or(0);
+    lines.insert(module_at, module);
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static DAYS"))
+        .ok_or("mod.rs: no DAYS")?;
+    let end = start + lines[start..].iter().position(|line| line == "];").ok_or("mod.rs: DAYS end?")?;
+    let solver_at = (start + 1..end)
+        .find(|i| day_of(&lines[*i], "    &day").is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(solver_at, solver);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds `pub mod yYYYY;` and the calendar of `year` to the text of `lib.rs`,
+/// keeping both lists in year order.
+pub fn register_year(lib: &str, year: u32) -> MyResult<String> {
+    let module = format!("pub mod y{};", year);
+    if lib.lines().any(|line| line == module) {
+        return Err(format!("{} is already registered", year).into());
+    }
+    let year_of = |line: &str, prefix: &str| -> Option<u32> { line.strip_prefix(prefix)?.get(..4)?.parse().ok() };
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+    let last_module = lines
+        .iter()
+        .rposition(|line| year_of(line, "pub mod y").is_some())
+        .ok_or("lib.rs: no 'pub mod yYYYY;' lines")?;
+    let module_at = lines
+        .iter()
+        .position(|line| year_of(line, "pub mod y").is_some_and(|y| y > year))
+        .unwrap_or(last_module + 1);
+    lines.insert(module_at, module);
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static YEARS"))
+        .ok_or("lib.rs: no YEARS")?;
+    let end = start + lines[start..].iter().position(|line| line == "];").ok_or("lib.rs: YEARS end?")?;
+    let calendar_at = (start + 1..end)
+        .find(|i| year_of(&lines[*i], "    &y").is_some_and(|y| y > year))
+        .unwrap_or(end);
+    lines.insert(calendar_at, format!("    &y{}::YEAR,", year));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates the module, input placeholder, examples and answers stubs of `day`
+/// of `year` under `root` and registers it in `src/yYYYY/mod.rs`, starting
+/// that year in `src/lib.rs` first if it is new. Nothing is written if any of
+/// the files already exists.
+pub fn new_day(root: &Path, year: u32, day: u32) -> MyResult<Vec<PathBuf>> {
+    let year_dir = root.join(format!("src/y{}", year));
+    let mod_path = year_dir.join("mod.rs");
+    let lib_path = root.join("src/lib.rs");
+    let (module, lib) = match mod_path.exists() {
+        true => (fs::read_to_string(&mod_path)?, None),
+        false => (
+            YEAR_TEMPLATE.replace("{Y}", &year.to_string()),
+            Some(register_year(&fs::read_to_string(&lib_path)?, year)?),
+        ),
+    };
+    let module = register(&module, day)?;
+    let template = TEMPLATE.replace("{Y}", &year.to_string()).replace("{N}", &day.to_string());
+    let files = [
+        (year_dir.join(format!("day{}.rs", day)), template),
+        (root.join(inputs::dir(year).join(format!("input{}.txt", day))), String::new()),
+        (root.join(examples::path(year, day)), EXAMPLES.into()),
+        (root.join(Answers::path(year, day)), Answers::default().to_string()),
+    ];
+    let existing: Vec<String> = files
+        .iter()
+        .filter(|(path, _)| path.exists())
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+    if !existing.is_empty() {
+        return Err(format!("refusing to overwrite {}", existing.join(", ")).into());
+    }
+    fs::create_dir_all(&year_dir)?;
+    fs::create_dir_all(root.join(inputs::dir(year)))?;
+    for (path, content) in &files {
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?
+            .write_all(content.as_bytes())?;
+    }
+    fs::write(&mod_path, module)?;
+    let mut written: Vec<PathBuf> = files.into_iter().map(|(path, _)| path).chain([mod_path]).collect();
+    if let Some(lib) = lib {
+        fs::write(&lib_path, lib)?;
+        written.push(lib_path);
+    }
+    Ok(written)
+}
+
+#[test]
+fn test_register() {
+    let lib = "pub mod solver;\npub mod day1;\npub mod day3;\n\npub static DAYS: &[&dyn Solver] = &[\n    &day1::Day1,\n    &day3::Day3,\n];\n";
+    assert_eq!(
+        register(lib, 2).unwrap(),
+        "pub mod solver;\npub mod day1;\npub mod day2;\npub mod day3;\n\npub static DAYS: &[&dyn Solver] = &[\n    &day1::Day1,\n    &day2::Day2,\n    &day3::Day3,\n];\n"
+    );
+    let lib = register(lib, 12).unwrap();
+    assert!(lib.contains("pub mod day3;\npub mod day12;\n"), "{}", lib);
+    assert!(lib.contains("    &day3::Day3,\n    &day12::Day12,\n];"), "{}", lib);
+    assert!(register(&lib, 12).is_err());
+
+    let first = register(&YEAR_TEMPLATE.replace("{Y}", "2024"), 1).unwrap();
+    assert!(first.starts_with("pub mod day1;\n\nuse crate::solver::Solver;"), "{}", first);
+    assert!(first.contains("= &[\n    &day1::Day1,\n];\n"), "{}", first);
+    assert!(first.ends_with("pub static YEAR: Year = Year::new(2024, DAYS);\n"), "{}", first);
+}
+
+#[test]
+fn test_register_year() {
+    let lib = "pub mod types;\npub mod y2023;\n\npub static YEARS: &[&Year] = &[\n    &y2023::YEAR,\n];\n";
+    let lib = register_year(lib, 2022).unwrap();
+    assert!(lib.contains("pub mod types;\npub mod y2022;\npub mod y2023;\n"), "{}", lib);
+    assert!(lib.contains("    &y2022::YEAR,\n    &y2023::YEAR,\n];"), "{}", lib);
+    assert!(register_year(&lib, 2023).is_err());
+}
+
+#[test]
+fn test_new_day_refuses_to_overwrite() {
+    let root = std::env::temp_dir().join(format!("rust-advent-scaffold-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src/y2023")).unwrap();
+    fs::create_dir_all(root.join("input/2023")).unwrap();
+    let lib = "pub mod y2023;\n\npub static YEARS: &[&Year] = &[\n    &y2023::YEAR,\n];\n";
+    let days = "pub mod day1;\n\npub static DAYS: &[&dyn Solver] = &[\n    &day1::Day1,\n];\n";
+    fs::write(root.join("src/lib.rs"), lib).unwrap();
+    fs::write(root.join("src/y2023/mod.rs"), days).unwrap();
+
+    fs::write(root.join("input/2023/input6.txt"), "mine").unwrap();
+    let err = new_day(&root, 2023, 6).unwrap_err().to_string();
+    assert!(err.starts_with("refusing to overwrite") && err.contains("input6.txt"), "{}", err);
+    assert_eq!(fs::read_to_string(root.join("src/y2023/mod.rs")).unwrap(), days);
+    assert!(!root.join("src/y2023/day6.rs").exists());
+
+    fs::remove_file(root.join("input/2023/input6.txt")).unwrap();
+    assert_eq!(new_day(&root, 2023, 6).unwrap().len(), 5);
+    let module = fs::read_to_string(root.join("src/y2023/day6.rs")).unwrap();
+    assert!(module.contains("impl Day for Day6 {") && module.contains("const YEAR: u32 = 2023;"));
+    assert_eq!(fs::read_to_string(root.join("input/2023/answers6.txt")).unwrap(), "p1:\np2:\n");
+    assert!(fs::read_to_string(root.join("src/y2023/mod.rs")).unwrap().contains("    &day6::Day6,\n"));
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib);
+    assert!(new_day(&root, 2023, 6).is_err());
+
+    // A new year gets its own module and calendar.
+    assert_eq!(new_day(&root, 2024, 1).unwrap().len(), 6);
+    assert!(root.join("input/2024/input1.txt").exists());
+    assert!(fs::read_to_string(root.join("src/y2024/mod.rs")).unwrap().contains("    &day1::Day1,\n"));
+    assert!(fs::read_to_string(root.join("src/lib.rs")).unwrap().contains("    &y2024::YEAR,\n"));
+    fs::remove_dir_all(&root).unwrap();
+}
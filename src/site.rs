@@ -0,0 +1,177 @@
+use crate::http;
+use crate::inputs::{Fetcher, InputKey};
+use crate::solver::{Answer, Part};
+use crate::types::MyResult;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+/// The puzzle website: real inputs are read from
+/// `{base_url}/{year}/day/{day}/input` and answers posted to `.../answer`.
+pub struct Site {
+    pub base_url: String,
+    pub session: String,
+}
+
+impl Site {
+    pub const BASE_URL: &'static str = "https://adventofcode.com";
+
+    /// Reads the session token from `AOC_SESSION` and the base url from
+    /// `AOC_BASE_URL`, unless given explicitly.
+    pub fn from_env(base_url: Option<&str>, session: Option<&str>) -> MyResult<Site> {
+        let session = match session {
+            Some(session) => session.to_string(),
+            None => env::var("AOC_SESSION").map_err(|_| "no session token: set AOC_SESSION or pass --session")?,
+        };
+        let base_url = match base_url {
+            Some(url) => url.to_string(),
+            None => env::var("AOC_BASE_URL").unwrap_or_else(|_| Site::BASE_URL.into()),
+        };
+        Ok(Site {
+            base_url: base_url.trim_end_matches('/').into(),
+            session,
+        })
+    }
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+    pub fn submit(&self, year: u32, day: u32, part: Part, answer: Answer) -> MyResult<Verdict> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = (part.index() + 1).to_string();
+        let answer = answer.to_string();
+        let form = [("level", level.as_str()), ("answer", answer.as_str())];
+        let response = http::post_form(&url, &[("Cookie", &self.cookie())], &form)?;
+        if response.status != 200 {
+            return Err(format!("{}: HTTP {}", url, response.status).into());
+        }
+        Verdict::of_response(&response.body)
+    }
+}
+
+impl Fetcher for Site {
+    fn fetch(&self, key: &InputKey) -> MyResult<String> {
+        if key.variant != "input" {
+            return Err(format!("only real inputs can be fetched, not {}", key).into());
+        }
+        let url = format!("{}/{}/day/{}/input", self.base_url, key.year, key.day);
+        let response = http::get(&url, &[("Cookie", &self.cookie())])?;
+        if response.status != 200 {
+            let reason = response.body.lines().next().unwrap_or("").trim();
+            return Err(format!("{}: HTTP {} {}", url, response.status, reason).into());
+        }
+        Ok(response.body)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint on which side.
+    Wrong,
+    /// Rate limited; nothing was checked.
+    Wait(u64),
+    /// The part was already solved on the site.
+    Solved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the HTML page returned for a submission.
+    pub fn of_response(body: &str) -> MyResult<Verdict> {
+        if body.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if body.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else if body.contains("That's not the right answer") {
+            Ok(Verdict::Wrong)
+        } else if body.contains("You gave an answer too recently") {
+            Ok(Verdict::Wait(wait_seconds(body).unwrap_or(60)))
+        } else if body.contains("Did you already complete it") {
+            Ok(Verdict::Solved)
+        } else {
+            Err("unrecognised answer page".into())
+        }
+    }
+    /// Whether the site actually judged the answer.
+    pub fn is_judged(&self) -> bool {
+        !matches!(self, Verdict::Wait(_) | Verdict::Solved)
+    }
+}
+
+/// Parses "You have 1m 5s left to wait" into seconds.
+fn wait_seconds(body: &str) -> Option<u64> {
+    let start = body.find("You have ")? + "You have ".len();
+    let end = start + body[start..].find(" left to wait")?;
+    body[start..end].split(' ').try_fold(0, |total, unit| {
+        let (value, scale) = match unit.strip_suffix('m') {
+            Some(minutes) => (minutes, 60),
+            None => (unit.strip_suffix('s')?, 1),
+        };
+        Some(total + value.parse::<u64>().ok()? * scale)
+    })
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(seconds) => write!(f, "wait {}s", seconds),
+            Verdict::Solved => write!(f, "already solved"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Verdict, String> {
+        match text {
+            "correct" => Ok(Verdict::Correct),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "already solved" => Ok(Verdict::Solved),
+            text => text
+                .strip_prefix("wait ")
+                .and_then(|wait| wait.strip_suffix('s'))
+                .and_then(|seconds| seconds.parse().ok())
+                .map(Verdict::Wait)
+                .ok_or_else(|| format!("verdict '{}'?", text)),
+        }
+    }
+}
+
+#[test]
+fn test_verdicts() {
+    let page = |text: &str| format!("<main><article><p>{}</p></article></main>", text);
+    let verdict = |text: &str| Verdict::of_response(&page(text)).unwrap();
+    assert_eq!(verdict("That's the right answer! You are one gold star closer."), Verdict::Correct);
+    assert_eq!(verdict("That's not the right answer; your answer is too high."), Verdict::TooHigh);
+    assert_eq!(verdict("That's not the right answer; your answer is too low."), Verdict::TooLow);
+    assert_eq!(verdict("That's not the right answer.  If you're stuck..."), Verdict::Wrong);
+    assert_eq!(
+        verdict("You gave an answer too recently; you have to wait. You have 1m 5s left to wait."),
+        Verdict::Wait(65)
+    );
+    assert_eq!(verdict("You don't seem to be solving the right level.  Did you already complete it?"), Verdict::Solved);
+    assert!(Verdict::of_response("<html></html>").is_err());
+    for verdict in [Verdict::Correct, Verdict::TooLow, Verdict::Wait(37), Verdict::Solved] {
+        assert_eq!(verdict.to_string().parse(), Ok(verdict));
+    }
+}
+
+#[test]
+fn test_submit() {
+    let (url, server) = http::mock_server(vec![(200, "<p>That's not the right answer; your answer is too low.</p>".into())]);
+    let site = Site { base_url: url, session: "s3cret".into() };
+    assert_eq!(site.submit(2023, 4, Part::Two, 1234).unwrap(), Verdict::TooLow);
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /2023/day/4/answer "));
+    assert!(requests[0].ends_with("level=2&answer=1234"));
+}
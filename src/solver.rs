@@ -0,0 +1,133 @@
+use crate::memory::{self, Usage};
+use crate::types::MyResult;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+pub type Answer = u64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn index(&self) -> usize {
+        match self {
+            Part::One => 0,
+            Part::Two => 1,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "p{}", self.index() + 1)
+    }
+}
+
+/// A puzzle day: the input is read once as a whole and parsed into a model
+/// shared by both parts. The model may borrow from the input instead of
+/// copying out of it; of the 2023 days only day 1 keeps text, its lines, and
+/// the others keep numbers parsed out of it, so their models ignore the
+/// lifetime.
+pub trait Day {
+    const YEAR: u32;
+    const DAY: u32;
+    type Model<'a>: PartialEq + fmt::Debug;
+
+    fn parse(input: &str) -> MyResult<Self::Model<'_>>;
+    /// Writes the model back as puzzle input that parses to an equal model.
+    fn print(model: &Self::Model<'_>) -> String;
+    fn p1(model: &Self::Model<'_>) -> MyResult<Answer>;
+    fn p2(model: &Self::Model<'_>) -> MyResult<Answer>;
+
+    fn part(model: &Self::Model<'_>, part: Part) -> MyResult<Answer> {
+        match part {
+            Part::One => Self::p1(model),
+            Part::Two => Self::p2(model),
+        }
+    }
+}
+
+/// One item per line, for models that are a list of lines.
+pub fn print_lines<T: fmt::Display>(items: &[T]) -> String {
+    items.iter().map(|item| format!("{}\n", item)).collect()
+}
+
+/// Answers and wall clock samples of one or more runs of a day, with the
+/// heap use of the first run when the counting allocator is installed.
+#[derive(Debug)]
+pub struct Run {
+    pub year: u32,
+    pub day: u32,
+    pub answers: [Result<Answer, String>; 2],
+    pub parse: Vec<Duration>,
+    pub parts: [Vec<Duration>; 2],
+    pub parse_memory: Option<Usage>,
+    pub part_memory: [Option<Usage>; 2],
+}
+
+/// Object safe view of a [`Day`], so days can be listed in a registry.
+pub trait Solver: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    /// Parses `input` and solves both parts `runs` times, timing each step.
+    fn run(&self, input: &str, runs: usize) -> MyResult<Run>;
+    /// Parses `input`, prints the model and checks that the printed text
+    /// parses back to the same model.
+    fn round_trip(&self, input: &str) -> MyResult<()>;
+}
+
+fn timed<T>(samples: &mut Vec<Duration>, usage: &mut Option<Usage>, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let (result, measured) = memory::measure(f);
+    samples.push(start.elapsed());
+    *usage = usage.or(measured);
+    result
+}
+
+impl<D: Day + Sync> Solver for D {
+    fn year(&self) -> u32 {
+        D::YEAR
+    }
+    fn day(&self) -> u32 {
+        D::DAY
+    }
+    fn run(&self, input: &str, runs: usize) -> MyResult<Run> {
+        let mut run = Run {
+            year: D::YEAR,
+            day: D::DAY,
+            answers: [Err("not run".into()), Err("not run".into())],
+            parse: vec![],
+            parts: [vec![], vec![]],
+            parse_memory: None,
+            part_memory: [None, None],
+        };
+        for _ in 0..runs.max(1) {
+            let model = timed(&mut run.parse, &mut run.parse_memory, || D::parse(input))?;
+            for part in Part::ALL {
+                let (samples, usage) = (&mut run.parts[part.index()], &mut run.part_memory[part.index()]);
+                let answer = timed(samples, usage, || D::part(&model, part));
+                run.answers[part.index()] = answer.map_err(|e| e.to_string());
+            }
+        }
+        Ok(run)
+    }
+    fn round_trip(&self, input: &str) -> MyResult<()> {
+        let printed = D::print(&D::parse(input)?);
+        reparses::<D>(input, &printed)
+    }
+}
+
+/// Both models borrow for the same lifetime, so that they can be compared.
+fn reparses<'a, D: Day>(input: &'a str, printed: &'a str) -> MyResult<()> {
+    let model = D::parse(input)?;
+    let again = D::parse(printed).map_err(|e| format!("printed model does not parse: {}", e))?;
+    match again == model {
+        true => Ok(()),
+        false => Err(format!("printed model parses differently:\n{}", printed).into()),
+    }
+}
@@ -0,0 +1,150 @@
+use crate::types::MyResult;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info,
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = Box<dyn Error>;
+
+    fn from_str(text: &str) -> MyResult<Level> {
+        match text {
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            text => Err(format!("trace level '{}'?", text).into()),
+        }
+    }
+}
+
+/// Which messages are shown: up to `default` for every target, and up to
+/// the given level for the listed targets. Days trace under `YEAR/dayN`
+/// targets, such as `2023/day5`; a listed target also covers the targets
+/// under it, so `2023` covers every day of 2023.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Filter {
+    pub default: Option<Level>,
+    pub targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    /// `-v` shows debug messages of every target, `-vv` everything.
+    pub fn verbosity(count: usize) -> Filter {
+        let default = match count {
+            0 => None,
+            1 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        };
+        Filter { default, targets: vec![] }
+    }
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        let max = self
+            .targets
+            .iter()
+            .filter(|(name, _)| covers(name, target))
+            .map(|(_, level)| *level)
+            .max()
+            .max(self.default);
+        max.is_some_and(|max| level <= max)
+    }
+}
+
+/// Whether the filter target `name` is `target` or one of its parents.
+fn covers(name: &str, target: &str) -> bool {
+    target
+        .strip_prefix(name)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// A comma separated list of `target`, `target=level` or a bare level that
+/// applies to every target, e.g. `2023/day5,2023/day4=debug`.
+impl FromStr for Filter {
+    type Err = Box<dyn Error>;
+
+    fn from_str(text: &str) -> MyResult<Filter> {
+        let mut filter = Filter::default();
+        for item in text.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            match item.split_once('=') {
+                Some((target, level)) => filter.targets.push((target.into(), level.parse()?)),
+                None => match item.parse() {
+                    Ok(level) => filter.default = filter.default.max(Some(level)),
+                    Err(_) => filter.targets.push((item.into(), Level::Trace)),
+                },
+            }
+        }
+        Ok(filter)
+    }
+}
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static FILTER: RwLock<Filter> = RwLock::new(Filter { default: None, targets: vec![] });
+
+/// Replaces the filter used by `trace!`. Nothing is shown until this is called.
+pub fn set(filter: Filter) {
+    ACTIVE.store(filter != Filter::default(), Ordering::Relaxed);
+    *FILTER.write().unwrap() = filter;
+}
+
+pub fn enabled(target: &str, level: Level) -> bool {
+    ACTIVE.load(Ordering::Relaxed) && FILTER.read().unwrap().enabled(target, level)
+}
+
+/// Writes to stderr, so answers on stdout stay machine readable.
+pub fn emit(target: &str, level: Level, message: fmt::Arguments) {
+    eprintln!("{:<5} {}: {}", level, target, message);
+}
+
+/// `trace!(Debug, "2023/day5", "{} translations", n)` emits the message if the
+/// filter enables it; the arguments are not formatted otherwise.
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $target:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($target, $crate::trace::Level::$level) {
+            $crate::trace::emit($target, $crate::trace::Level::$level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[test]
+fn test_filter() {
+    let filter: Filter = "2023/day5, 2023/day4=debug".parse().unwrap();
+    assert!(filter.enabled("2023/day5", Level::Trace));
+    assert!(filter.enabled("2023/day4", Level::Debug));
+    assert!(!filter.enabled("2023/day4", Level::Trace));
+    assert!(!filter.enabled("2023/day1", Level::Info));
+    assert!(!filter.enabled("2022/day5", Level::Info));
+
+    let filter: Filter = "info,2023/day5=trace".parse().unwrap();
+    assert!(filter.enabled("2023/day1", Level::Info));
+    assert!(!filter.enabled("2023/day1", Level::Debug));
+    assert!(filter.enabled("2023/day5", Level::Trace));
+
+    let filter: Filter = "2023=debug".parse().unwrap();
+    assert!(filter.enabled("2023/day4", Level::Debug));
+    assert!(!filter.enabled("2023/day4", Level::Trace));
+    assert!(!filter.enabled("2022/day4", Level::Info));
+    assert!(!filter.enabled("20234/day4", Level::Info));
+
+    assert!(Filter::verbosity(1).enabled("day1", Level::Debug));
+    assert!(!Filter::verbosity(1).enabled("day1", Level::Trace));
+    assert!(!Filter::verbosity(0).enabled("day1", Level::Info));
+    assert!("day5=loud".parse::<Filter>().is_err());
+}
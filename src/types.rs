@@ -1,3 +1,35 @@
 use std::error::Error;
 
 pub type MyResult<T> = Result<T, Box<dyn Error>>;
+
+/// Turns the result of a `checked_*` operation into an error on overflow,
+/// so puzzle totals never silently wrap.
+pub fn checked<T>(value: Option<T>) -> MyResult<T> {
+    value.ok_or_else(|| "arithmetic overflow".into())
+}
+
+/// Parses ASCII digits straight from bytes, without the sign and UTF-8
+/// handling of `str::parse`. Anything but one or more digits is an error.
+pub fn parse_uint<T: TryFrom<u64>>(digits: &[u8]) -> MyResult<T> {
+    let number = || format!("number '{}'?", String::from_utf8_lossy(digits));
+    if digits.is_empty() {
+        return Err(number().into());
+    }
+    let value = digits.iter().try_fold(0u64, |value, byte| -> MyResult<u64> {
+        match byte {
+            b'0'..=b'9' => checked(value.checked_mul(10).and_then(|value| value.checked_add(u64::from(byte - b'0')))),
+            _ => Err(number().into()),
+        }
+    })?;
+    T::try_from(value).map_err(|_| format!("number {} too large", value).into())
+}
+
+#[test]
+fn test_parse_uint() {
+    assert_eq!(parse_uint::<u64>(b"0042").unwrap(), 42);
+    assert_eq!(parse_uint::<u64>(b"18446744073709551615").unwrap(), u64::MAX);
+    assert_eq!(parse_uint::<u64>(b"18446744073709551616").unwrap_err().to_string(), "arithmetic overflow");
+    assert_eq!(parse_uint::<u32>(b"4294967296").unwrap_err().to_string(), "number 4294967296 too large");
+    assert_eq!(parse_uint::<u32>(b"+1").unwrap_err().to_string(), "number '+1'?");
+    assert!(parse_uint::<u32>(b"").is_err());
+}
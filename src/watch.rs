@@ -0,0 +1,91 @@
+use crate::solver::{Answer, Part};
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Files whose modification times are compared between polls. A missing file
+/// has no time, so creating or deleting one also counts as a change.
+pub struct Watched {
+    paths: Vec<PathBuf>,
+    times: Vec<Option<SystemTime>>,
+}
+
+impl Watched {
+    pub fn new(paths: Vec<PathBuf>) -> Watched {
+        let times = Watched::times(&paths);
+        Watched { paths, times }
+    }
+    fn times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+        paths
+            .iter()
+            .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+            .collect()
+    }
+    /// The files changed since the last poll.
+    pub fn poll(&mut self) -> Vec<&PathBuf> {
+        let times = Watched::times(&self.paths);
+        let changed = self
+            .paths
+            .iter()
+            .zip(times.iter().zip(&self.times))
+            .filter(|(_, (now, before))| now != before)
+            .map(|(path, _)| path)
+            .collect();
+        self.times = times;
+        changed
+    }
+}
+
+/// One part's answer against the previous run and the expected answer. The
+/// line starts with `!` when it differs from the expected answer, `~` when it
+/// only differs from the previous run, and a space otherwise.
+pub fn line(
+    day: u32,
+    part: Part,
+    answer: &Result<Answer, String>,
+    previous: Option<&Result<Answer, String>>,
+    expected: Option<Answer>,
+) -> String {
+    let show = |answer: &Result<Answer, String>| match answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e),
+    };
+    let marker = match (expected, previous) {
+        (Some(expected), _) if *answer != Ok(expected) => '!',
+        (_, Some(previous)) if previous != answer => '~',
+        _ => ' ',
+    };
+    let mut line = format!("{} day{}{}: {}", marker, day, part, show(answer));
+    if let Some(previous) = previous {
+        line += &format!("  previous {}", show(previous));
+    }
+    if let Some(expected) = expected {
+        line += &format!("  expected {}", expected);
+    }
+    line
+}
+
+#[test]
+fn test_line() {
+    assert_eq!(line(1, Part::One, &Ok(7), None, None), "  day1p1: 7");
+    assert_eq!(line(1, Part::One, &Ok(7), Some(&Ok(7)), Some(7)), "  day1p1: 7  previous 7  expected 7");
+    assert_eq!(line(1, Part::Two, &Ok(8), Some(&Ok(7)), None), "~ day1p2: 8  previous 7");
+    assert_eq!(line(1, Part::Two, &Ok(8), Some(&Ok(8)), Some(7)), "! day1p2: 8  previous 8  expected 7");
+    assert_eq!(
+        line(2, Part::One, &Err("bad".into()), Some(&Ok(7)), Some(7)),
+        "! day2p1: error: bad  previous 7  expected 7"
+    );
+}
+
+#[test]
+fn test_poll() {
+    let path = std::env::temp_dir().join(format!("rust-advent-watch-{}.txt", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let mut watched = Watched::new(vec![path.clone()]);
+    assert!(watched.poll().is_empty());
+    fs::write(&path, "1").unwrap();
+    assert_eq!(watched.poll(), [&path]);
+    assert!(watched.poll().is_empty());
+    fs::remove_file(&path).unwrap();
+    assert_eq!(watched.poll(), [&path]);
+}
@@ -0,0 +1,165 @@
+use super::day1::WORDS;
+use crate::gen::Rng;
+use std::collections::{HashMap, HashSet};
+
+/// `input` of `day` with its numbers and letters changed.
+pub fn anonymise(day: u32, rng: &mut Rng, input: &str) -> Option<String> {
+    match day {
+        1 => Some(day1(rng, input)),
+        2 => Some(day2(rng, input)),
+        3 => Some(day3(rng, input)),
+        4 => Some(day4(rng, input)),
+        5 => Some(day5(rng, input)),
+        _ => None,
+    }
+}
+
+/// Letters that are in no digit word, so putting them anywhere neither
+/// makes nor breaks one.
+const FILLER: [char; 12] = ['a', 'b', 'c', 'd', 'j', 'k', 'l', 'm', 'p', 'q', 'y', 'z'];
+
+/// Letters outside digit words are replaced; digits and words stay put.
+fn day1(rng: &mut Rng, input: &str) -> String {
+    let mut output = String::new();
+    for line in input.split_inclusive('\n') {
+        let mut kept = vec![false; line.len()];
+        for (i, _) in line.char_indices() {
+            if let Some(word) = WORDS.iter().find(|word| line[i..].starts_with(*word)) {
+                kept[i..i + word.len()].fill(true);
+            }
+        }
+        for (i, c) in line.char_indices() {
+            output.push(match c {
+                c if c.is_ascii_lowercase() && !kept[i] => *rng.pick(&FILLER),
+                c if c.is_ascii_uppercase() => rng.pick(&FILLER).to_ascii_uppercase(),
+                c => c,
+            });
+        }
+    }
+    output
+}
+
+/// Game ids are dealt out again in shuffled order, and so are the rounds of
+/// each game and the colours of each round.
+fn day2(rng: &mut Rng, input: &str) -> String {
+    let id = |line: &str| -> Option<u64> { line.strip_prefix("Game ")?.split_once(':')?.0.parse().ok() };
+    let mut ids: Vec<u64> = input.lines().filter_map(id).collect();
+    rng.shuffle(&mut ids);
+    let mut ids = ids.into_iter();
+    let mut output = String::new();
+    for line in input.split_inclusive('\n') {
+        let (Some(_), Some((_, rounds))) = (id(line), line.split_once(':')) else {
+            output += line;
+            continue;
+        };
+        let mut rounds: Vec<String> = rounds
+            .trim_end_matches('\n')
+            .split(';')
+            .map(|round| {
+                let mut colours: Vec<&str> = round.split(',').map(str::trim).collect();
+                rng.shuffle(&mut colours);
+                colours.join(", ")
+            })
+            .collect();
+        rng.shuffle(&mut rounds);
+        output += &format!("Game {}: {}", ids.next().unwrap_or_default(), rounds.join("; "));
+        if line.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+    output
+}
+
+const SYMBOLS: [char; 9] = ['#', '$', '%', '&', '+', '-', '/', '=', '@'];
+
+/// Digits 1 to 9 are permuted, zero staying zero so no number gains or loses
+/// a leading zero, and symbols other than gears are swapped for others.
+fn day3(rng: &mut Rng, input: &str) -> String {
+    let mut digits: Vec<char> = ('1'..='9').collect();
+    rng.shuffle(&mut digits);
+    input
+        .chars()
+        .map(|c| match c {
+            '1'..='9' => digits[c as usize - '1' as usize],
+            '0' | '.' | '*' | '\n' | '\r' => c,
+            c if c.is_ascii_punctuation() => *rng.pick(&SYMBOLS),
+            c => c,
+        })
+        .collect()
+}
+
+/// The numbers of each card are renamed, the same way on both sides so the
+/// matches stay, and each side is shuffled. Card ids are kept in order.
+fn day4(rng: &mut Rng, input: &str) -> String {
+    let mut output = String::new();
+    for line in input.split_inclusive('\n') {
+        let sides = line.split_once(':').and_then(|(card, numbers)| {
+            let (winners, drawn) = numbers.split_once('|')?;
+            let numbers = |side: &str| side.split_whitespace().map(|n| n.parse().ok()).collect::<Option<Vec<u32>>>();
+            Some((card, numbers(winners)?, numbers(drawn)?))
+        });
+        let Some((card, mut winners, mut drawn)) = sides else {
+            output += line;
+            continue;
+        };
+        let distinct: HashSet<u32> = winners.iter().chain(&drawn).copied().filter(|n| *n > 0).collect();
+        let max = distinct.iter().copied().max().unwrap_or(0).max(99);
+        let mut names: HashMap<u32, u32> = HashMap::from([(0, 0)]);
+        let mut taken = HashSet::new();
+        for n in distinct {
+            let mut name = rng.between(1, max.into()) as u32;
+            while !taken.insert(name) {
+                name = rng.between(1, max.into()) as u32;
+            }
+            names.insert(n, name);
+        }
+        let width = max.to_string().len();
+        let mut side = |numbers: &mut Vec<u32>| {
+            rng.shuffle(numbers);
+            numbers.iter().map(|n| format!(" {:>width$}", names[n], width = width)).collect::<String>()
+        };
+        output += &format!("{}:{} |{}", card, side(&mut winners), side(&mut drawn));
+        if line.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Seed range starts, destinations and sources all move up by one offset,
+/// which moves every location by the same amount; range lengths stay, and
+/// the translations of each map are shuffled.
+fn day5(rng: &mut Rng, input: &str) -> String {
+    let offset = rng.between(1, 1_000_000_000) as i64;
+    let shift = |number: &str| match number.parse::<i64>().ok().and_then(|n| n.checked_add(offset)) {
+        Some(n) => n.to_string(),
+        None => number.to_string(),
+    };
+    let mut output = String::new();
+    let mut translations: Vec<String> = vec![];
+    for line in input.split_inclusive('\n') {
+        let numbers: Vec<&str> = line.split_whitespace().collect();
+        if let [dest, from, size] = numbers[..] {
+            if numbers.iter().all(|n| n.parse::<i64>().is_ok()) {
+                translations.push(format!("{} {} {}\n", shift(dest), shift(from), size));
+                continue;
+            }
+        }
+        rng.shuffle(&mut translations);
+        output.extend(translations.drain(..));
+        match line.strip_prefix("seeds:") {
+            Some(seeds) => {
+                let seeds: Vec<String> = seeds
+                    .split_whitespace()
+                    .enumerate()
+                    .map(|(i, n)| if i % 2 == 0 { shift(n) } else { n.to_string() })
+                    .collect();
+                output += &format!("seeds: {}\n", seeds.join(" "));
+            }
+            None => output += line,
+        }
+    }
+    rng.shuffle(&mut translations);
+    output.extend(translations);
+    output
+}
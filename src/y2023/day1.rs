@@ -0,0 +1,116 @@
+use crate::solver::{print_lines, Answer, Day};
+use crate::types::{checked, MyResult};
+fn first(value_map: &[(&str, i32)], line: &str) -> MyResult<i32> {
+    value_map
+        .iter()
+        .map(|(name, value)| (*value, line.find(name)))
+        .fold(None::<(i32, usize)>, |acc, (val, pos)| {
+            acc.and_then(|acc| {
+                pos.map(|pos| if acc.1 < pos { acc } else { (val, pos) })
+                    .or(Some(acc))
+            })
+                .or_else(|| pos.map(|pos| (val, pos)))
+        })
+        .map(|(value, _)| value)
+        .ok_or_else(|| "No first".into())
+}
+
+fn last(value_map: &[(&str, i32)], line: &str) -> MyResult<i32> {
+    value_map
+        .iter()
+        .map(|(name, value)| (*value, line.rfind(name)))
+        .fold(None::<(i32, usize)>, |acc, (val, pos)| {
+            acc.and_then(|acc| {
+                pos.map(|pos| if acc.1 > pos { acc } else { (val, pos) })
+                    .or(Some(acc))
+            })
+                .or_else(|| pos.map(|pos| (val, pos)))
+        })
+        .map(|(value, _)| value)
+        .ok_or_else(|| "No last".into())
+}
+
+fn calibration_sum(lines: &[&str], value_map: &[(&str, i32)]) -> MyResult<u64> {
+    lines
+        .iter()
+        .map(|line| -> MyResult<u64> {
+            let first = first(value_map, line)?;
+            let last = last(value_map, line)?;
+            Ok((first * 10 + last).try_into()?)
+        })
+        .try_fold(0u64, |a, b| checked(a.checked_add(b?)))
+}
+
+pub struct Day1;
+
+impl Day for Day1 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 1;
+    type Model<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> MyResult<Vec<&str>> {
+        Ok(input.lines().collect())
+    }
+
+    fn print(lines: &Vec<&str>) -> String {
+        print_lines(lines)
+    }
+
+    fn p1(lines: &Vec<&str>) -> MyResult<Answer> {
+        let value_map_p1 = [
+            ("1", 1),
+            ("2", 2),
+            ("3", 3),
+            ("4", 4),
+            ("5", 5),
+            ("6", 6),
+            ("7", 7),
+            ("8", 8),
+            ("9", 9),
+        ];
+        calibration_sum(lines, &value_map_p1)
+    }
+
+    fn p2(lines: &Vec<&str>) -> MyResult<Answer> {
+        let value_map_p2 = [
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+            ("1", 1),
+            ("2", 2),
+            ("3", 3),
+            ("4", 4),
+            ("5", 5),
+            ("6", 6),
+            ("7", 7),
+            ("8", 8),
+            ("9", 9),
+        ];
+        calibration_sum(lines, &value_map_p2)
+    }
+}
+
+pub const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Digit that `rest` starts with, also spelled out when `words` is set.
+fn digit_at(rest: &str, words: bool) -> Option<u64> {
+    let digit = rest.chars().next()?.to_digit(10).filter(|d| *d > 0).map(u64::from);
+    let word = || (1..).zip(WORDS).find(|(_, word)| rest.starts_with(word)).map(|(d, _)| d);
+    digit.or_else(|| words.then(word).flatten())
+}
+
+/// Reference calibration sum that tries every position of every line.
+pub fn calibration_scan(input: &str, words: bool) -> MyResult<Answer> {
+    input.lines().try_fold(0u64, |sum, line| {
+        let mut digits = line.char_indices().filter_map(|(i, _)| digit_at(&line[i..], words));
+        let first = digits.next().ok_or("No first")?;
+        let last = digits.next_back().unwrap_or(first);
+        checked(sum.checked_add(first * 10 + last))
+    })
+}
@@ -0,0 +1,154 @@
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use crate::solver::{print_lines, Answer, Day};
+use crate::types::{checked, parse_uint, MyResult};
+
+#[derive(Debug, PartialEq)]
+pub struct Round {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+impl Round {
+    fn add_round(mut self, round: MyResult<(&str, u32)>) -> MyResult<Round> {
+        let (color, count) = round?;
+        let total = match color {
+            "red" => &mut self.red,
+            "green" => &mut self.green,
+            "blue" => &mut self.blue,
+            color => return Err(format!("colour '{}'?", color).into()),
+        };
+        *total = checked(total.checked_add(count))?;
+        Ok(self)
+    }
+    fn new(red: u32, green: u32, blue: u32) -> Round {
+        Round { red, green, blue }
+    }
+    pub fn power(&self) -> MyResult<u64> {
+        let power = u64::from(self.red).checked_mul(self.green.into());
+        checked(power.and_then(|power| power.checked_mul(self.blue.into())))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Game {
+    pub id: u64,
+    pub rounds: Vec<Round>,
+}
+
+impl FromStr for Game {
+    type Err = Box<dyn Error>;
+
+    fn from_str(line: &str) -> MyResult<Game> {
+        let colon = line.find(':').ok_or("no colon")?;
+        let id = parse_uint(line[..colon].strip_prefix("Game ").ok_or("no 'Game '")?.as_bytes())?;
+        let rounds = line[colon + 1..]
+            .split(';')
+            .map(|part| -> MyResult<Round> {
+                part.split(',')
+                    .map(|round| -> MyResult<_> {
+                        let round = round.trim();
+                        let space = round.find(' ').ok_or("no space")?;
+                        Ok((&round[space + 1..], parse_uint(&round.as_bytes()[..space])?))
+                    })
+                    .try_fold(Round::new(0, 0, 0), Round::add_round)
+            })
+            .collect::<MyResult<_>>()?;
+        Ok(Game { id, rounds })
+    }
+}
+
+/// Only the colours that were drawn, or `0 red` if none was.
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let colours = [(self.red, "red"), (self.green, "green"), (self.blue, "blue")];
+        let drawn: Vec<String> = colours
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, colour)| format!("{} {}", count, colour))
+            .collect();
+        match drawn.is_empty() {
+            true => write!(f, "0 red"),
+            false => write!(f, "{}", drawn.join(", ")),
+        }
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounds: Vec<String> = self.rounds.iter().map(Round::to_string).collect();
+        write!(f, "Game {}: {}", self.id, rounds.join("; "))
+    }
+}
+
+impl Game {
+    pub fn is_possible(&self) -> bool {
+        self.rounds
+            .iter()
+            .all(|Round { red, green, blue }| *red <= 12 && *green <= 13 && *blue <= 14)
+    }
+    pub fn min_round(&self) -> Round {
+        self.rounds
+            .iter()
+            .fold(Round::new(0, 0, 0), |max, Round { red, green, blue }| Round {
+                red: cmp::max(*red, max.red),
+                green: cmp::max(*green, max.green),
+                blue: cmp::max(*blue, max.blue),
+            })
+    }
+}
+
+pub struct Day2;
+
+impl Day for Day2 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 2;
+    type Model<'a> = Vec<Game>;
+
+    fn parse(input: &str) -> MyResult<Vec<Game>> {
+        input.lines().map(Game::from_str).collect()
+    }
+
+    fn print(games: &Vec<Game>) -> String {
+        print_lines(games)
+    }
+
+    fn p1(games: &Vec<Game>) -> MyResult<Answer> {
+        games
+            .iter()
+            .filter(|game| game.is_possible())
+            .try_fold(0u64, |sum, game| checked(sum.checked_add(game.id)))
+    }
+
+    fn p2(games: &Vec<Game>) -> MyResult<Answer> {
+        games
+            .iter()
+            .try_fold(0u64, |sum, game| checked(sum.checked_add(game.min_round().power()?)))
+    }
+}
+
+#[test]
+fn test_sum_power_past_u32() {
+    let games = "Game 1: 100000 red, 100000 green, 100000 blue\nGame 2: 1 red, 1 green, 1 blue";
+    let games = Day2::parse(games).unwrap();
+    assert_eq!(Day2::p2(&games).unwrap(), 1_000_000_000_000_001);
+}
+
+#[test]
+fn test_sum_power_overflow() {
+    let game = "Game 1: 4000000000 red, 4000000000 green, 4000000000 blue";
+    let games = Day2::parse(game).unwrap();
+    assert_eq!(Day2::p2(&games).unwrap_err().to_string(), "arithmetic overflow");
+    let game = "Game 1: 4000000000 red, 4000000000 red";
+    assert_eq!(Day2::parse(game).unwrap_err().to_string(), "arithmetic overflow");
+}
+
+#[test]
+fn test_print_game() {
+    let games = Day2::parse("Game 7: 4 blue, 0 green, 1 red; 0 blue\n").unwrap();
+    assert_eq!(Day2::print(&games), "Game 7: 1 red, 4 blue; 0 red\n");
+    assert_eq!(Day2::parse(&Day2::print(&games)).unwrap(), games);
+}
@@ -0,0 +1,224 @@
+use crate::solver::{print_lines, Answer, Day};
+use crate::types::{checked, parse_uint, MyResult};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub struct Part {
+    pub start: i32,
+    pub end: i32,
+    pub value: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Line {
+    pub parts: Vec<Part>,
+    pub symbols: Vec<i32>,
+    pub gears: Vec<i32>,
+}
+
+impl Line {
+    fn find_parts(&self, symbols: &[&i32]) -> MyResult<u64> {
+        self.parts
+            .iter()
+            .filter(|part| {
+                symbols
+                    .iter()
+                    .any(|symbol| (part.start - 1..=part.end + 1).contains(*symbol))
+            })
+            .try_fold(0u64, |sum, part| checked(sum.checked_add(part.value)))
+    }
+}
+
+impl FromStr for Line {
+    type Err = Box<dyn Error>;
+
+    fn from_str(chars: &str) -> MyResult<Line> {
+        let (line, _) = chars.char_indices().try_fold(
+            (
+                Line {
+                    parts: vec![],
+                    symbols: vec![],
+                    gears: vec![],
+                },
+                None::<usize>,
+            ),
+            |(mut line, mut start_part), (index, char)| -> MyResult<_> {
+                if char.is_ascii_digit() {
+                    start_part = start_part.or(Some(index))
+                } else {
+                    if let Some(start) = start_part {
+                        line.parts.push(Part {
+                            start: start.try_into()?,
+                            end: (index - 1).try_into()?,
+                            value: parse_uint(&chars.as_bytes()[start..index])?,
+                        })
+                    }
+                    start_part = None;
+                    if char != '.' {
+                        line.symbols.push(index.try_into()?)
+                    }
+                    if char == '*' {
+                        line.gears.push(index.try_into()?)
+                    }
+                }
+                if index == chars.len() - 1 {
+                    if let Some(start) = start_part {
+                        line.parts.push(Part {
+                            start: (start).try_into()?,
+                            end: (chars.len() - 1).try_into()?,
+                            value: parse_uint(&chars.as_bytes()[start..])?,
+                        });
+                    }
+                }
+                Ok((line, start_part))
+            },
+        )?;
+        Ok(line)
+    }
+}
+
+/// The number, zero padded to the columns it spans.
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = (self.end - self.start + 1).max(1) as usize;
+        write!(f, "{:0width$}", self.value, width = width)
+    }
+}
+
+/// Gears print as `*` and every other symbol as `#`; the line ends at its
+/// last part or symbol.
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ends = self.parts.iter().map(|part| part.end).chain(self.symbols.iter().copied());
+        let mut line = vec!['.'; ends.max().map_or(0, |end| end as usize + 1)];
+        for symbol in &self.symbols {
+            line[*symbol as usize] = if self.gears.contains(symbol) { '*' } else { '#' };
+        }
+        for part in &self.parts {
+            for (i, digit) in part.to_string().chars().enumerate() {
+                line[part.start as usize + i] = digit;
+            }
+        }
+        write!(f, "{}", line.into_iter().collect::<String>())
+    }
+}
+
+fn sum_line_p1(prev: Option<&Line>, curr: &Line, next: Option<&Line>) -> MyResult<u64> {
+    let empty = vec![];
+    let curr_symbols: &Vec<i32> = &curr.symbols;
+    let prev_symbols = if let Some(prev) = prev {
+        &prev.symbols
+    } else {
+        &empty
+    };
+    let next_symbols = if let Some(next) = next {
+        &next.symbols
+    } else {
+        &empty
+    };
+    let symbols: Vec<&i32> = prev_symbols
+        .iter()
+        .chain(curr_symbols)
+        .chain(next_symbols)
+        .collect();
+    curr.find_parts(&symbols[..])
+}
+
+/// Sums `sum_line` over each line with its neighbours above and below.
+fn sum_lines(
+    lines: &[Line],
+    sum_line: fn(Option<&Line>, &Line, Option<&Line>) -> MyResult<u64>,
+) -> MyResult<u64> {
+    lines.iter().enumerate().try_fold(0u64, |sum, (i, curr)| {
+        let prev = i.checked_sub(1).map(|prev| &lines[prev]);
+        let line_sum = sum_line(prev, curr, lines.get(i + 1))?;
+        checked(sum.checked_add(line_sum))
+    })
+}
+
+fn part_matches(i: i32) -> impl Fn(&&Part) -> bool {
+    move |a: &&Part| a.start - 1 <= i && i <= a.end + 1
+}
+
+fn sum_line_p2(prev: Option<&Line>, curr: &Line, next: Option<&Line>) -> MyResult<u64> {
+    curr.gears
+        .iter()
+        .map(|i| {
+            let empty: Vec<u64> = vec![];
+            let prev_count: Vec<u64> = prev.map_or(empty.clone(), |a| {
+                a.parts
+                    .iter()
+                    .filter(part_matches(*i))
+                    .map(|p| p.value)
+                    .collect()
+            });
+            let next_count: Vec<u64> = next.map_or(empty, |a| {
+                a.parts
+                    .iter()
+                    .filter(part_matches(*i))
+                    .map(|p| p.value)
+                    .collect()
+            });
+            let curr_count: Vec<u64> = curr
+                .parts
+                .iter()
+                .filter(part_matches(*i))
+                .map(|p| p.value)
+                .collect();
+            [prev_count, next_count, curr_count].concat()
+        })
+        .filter(|v| v.len() >= 2)
+        .map(|v| {
+            v.iter()
+                .try_fold(1u64, |product, value| checked(product.checked_mul(*value)))
+        })
+        .try_fold(0u64, |sum, ratio| checked(sum.checked_add(ratio?)))
+}
+
+pub struct Day3;
+
+impl Day for Day3 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 3;
+    type Model<'a> = Vec<Line>;
+
+    fn parse(input: &str) -> MyResult<Vec<Line>> {
+        input.lines().map(Line::from_str).collect()
+    }
+
+    fn print(lines: &Vec<Line>) -> String {
+        print_lines(lines)
+    }
+
+    fn p1(lines: &Vec<Line>) -> MyResult<Answer> {
+        sum_lines(lines, sum_line_p1)
+    }
+
+    fn p2(lines: &Vec<Line>) -> MyResult<Answer> {
+        sum_lines(lines, sum_line_p2)
+    }
+}
+
+#[test]
+fn test_sum_gears_past_u32() {
+    let grid = "99999.....\n.....*....\n......99999";
+    let lines = Day3::parse(grid).unwrap();
+    assert_eq!(Day3::p2(&lines).unwrap(), 9_999_800_001);
+}
+
+#[test]
+fn test_sum_parts_overflow() {
+    let grid = "10000000000000000000*10000000000000000000";
+    let lines = Day3::parse(grid).unwrap();
+    assert_eq!(Day3::p1(&lines).unwrap_err().to_string(), "arithmetic overflow");
+}
+
+#[test]
+fn test_print_lines() {
+    let lines = Day3::parse("..007..+.\n.*....12\n").unwrap();
+    assert_eq!(lines[1].parts[0], Part { start: 6, end: 7, value: 12 });
+    assert_eq!(Day3::print(&lines), "..007..#\n.*....12\n");
+    assert_eq!(Day3::parse(&Day3::print(&lines)).unwrap(), lines);
+}
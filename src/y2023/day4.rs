@@ -0,0 +1,367 @@
+use std::cmp;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use crate::solver::{print_lines, Answer, Day};
+use crate::types::{checked, parse_uint, MyResult};
+
+/// Card numbers below this go in a bitset; puzzle inputs stay under 100.
+const SMALL: u32 = 128;
+
+/// Set of card numbers: one bit per value below [`SMALL`], so that lookups
+/// are cheap, and a hash set for larger ones, so that a stray big number
+/// cannot make the set huge.
+#[derive(Debug, Default, PartialEq)]
+struct NumberSet {
+    small: [u64; 2],
+    large: HashSet<u32>,
+}
+
+impl NumberSet {
+    fn insert(&mut self, value: u32) {
+        match value < SMALL {
+            true => self.small[(value / 64) as usize] |= 1 << (value % 64),
+            false => {
+                self.large.insert(value);
+            }
+        }
+    }
+    /// Values in increasing order.
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        let mut large: Vec<u32> = self.large.iter().copied().collect();
+        large.sort_unstable();
+        (0..SMALL).filter(|value| self.contains(*value)).chain(large)
+    }
+    fn contains(&self, value: u32) -> bool {
+        match value < SMALL {
+            true => self.small[(value / 64) as usize] & (1 << (value % 64)) != 0,
+            false => self.large.contains(&value),
+        }
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut set = NumberSet::default();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub id: usize,
+    winners: NumberSet,
+    pub drawn: Vec<u32>,
+}
+
+impl FromStr for Card {
+    type Err = Box<dyn Error>;
+
+    fn from_str(line: &str) -> MyResult<Card> {
+        let colon = line.find(':').ok_or("Colon?")?;
+        let pipe = line.find('|').ok_or("Pipe?")?;
+        let id = parse_uint(line[..colon].strip_prefix("Card").ok_or("Card?")?.trim().as_bytes())?;
+        Ok(Card {
+            id,
+            winners: numbers_in(&line[colon + 1..pipe])?.into_iter().collect(),
+            drawn: numbers_in(&line[pipe + 1..])?,
+        })
+    }
+}
+
+/// Winning numbers come out sorted, without repeats.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let numbers = |numbers: &mut dyn Iterator<Item = u32>| {
+            numbers.map(|n| format!(" {:>2}", n)).collect::<String>()
+        };
+        write!(
+            f,
+            "Card {}:{} |{}",
+            self.id,
+            numbers(&mut self.winners.iter()),
+            numbers(&mut self.drawn.iter().copied())
+        )
+    }
+}
+
+impl Card {
+    pub fn matches(&self) -> usize {
+        self.drawn
+            .iter()
+            .filter(|d| self.winners.contains(**d))
+            .count()
+    }
+    pub fn points(&self) -> MyResult<u64> {
+        match self.matches() {
+            0 => Ok(0),
+            count => checked(1u64.checked_shl((count - 1).try_into()?)),
+        }
+    }
+}
+
+pub fn numbers_in(chars: &str) -> MyResult<Vec<u32>> {
+    chars
+        .split(' ')
+        .filter(|l| !l.is_empty())
+        .map(|a| parse_uint(a.as_bytes()))
+        .collect()
+}
+
+/// Checks that cards are numbered 1, 2, 3... in input order.
+fn check_ids(cards: &[Card]) -> MyResult<()> {
+    for (i, card) in cards.iter().enumerate() {
+        let expected = i + 1;
+        if card.id == expected {
+            continue;
+        }
+        return Err(match i.checked_sub(1).map(|prev| cards[prev].id) {
+            Some(prev) if prev == card.id => format!("duplicate card {}", card.id),
+            _ if card.id < expected => format!("card {} out of order, expected {}", card.id, expected),
+            _ => format!("missing card {}, found card {}", expected, card.id),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Counts original cards plus won copies. Copies are only won of cards that
+/// exist, so wins running past the last card are dropped.
+pub fn total_cards(cards: &[Card]) -> MyResult<u64> {
+    check_ids(cards)?;
+    let mut copies = vec![1u64; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        let end = cmp::min(i + 1 + card.matches(), cards.len());
+        let win = copies[i];
+        for copy in &mut copies[i + 1..end] {
+            *copy = checked(copy.checked_add(win))?;
+        }
+        crate::trace!(Trace, "2023/day4", "card {}: {} copies, wins {} more", card.id, win, end - i - 1);
+    }
+    copies
+        .iter()
+        .try_fold(0u64, |sum, copy| checked(sum.checked_add(*copy)))
+}
+
+pub struct Day4;
+
+impl Day for Day4 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 4;
+    type Model<'a> = Vec<Card>;
+
+    fn parse(input: &str) -> MyResult<Vec<Card>> {
+        input.lines().map(Card::from_str).collect()
+    }
+
+    fn print(cards: &Vec<Card>) -> String {
+        print_lines(cards)
+    }
+
+    fn p1(cards: &Vec<Card>) -> MyResult<Answer> {
+        cards
+            .iter()
+            .try_fold(0u64, |sum, card| checked(sum.checked_add(card.points()?)))
+    }
+
+    fn p2(cards: &Vec<Card>) -> MyResult<Answer> {
+        total_cards(cards)
+    }
+}
+
+fn matches_naive(winners: &[u32], drawn: &[u32]) -> usize {
+    drawn.iter().filter(|d| winners.contains(d)).count()
+}
+
+/// Matches of each card, with the numbers kept in plain lists.
+fn matches_of(input: &str) -> MyResult<Vec<usize>> {
+    input
+        .lines()
+        .map(|line| -> MyResult<usize> {
+            let (_, numbers) = line.split_once(':').ok_or("Colon?")?;
+            let (winners, drawn) = numbers.split_once('|').ok_or("Pipe?")?;
+            Ok(matches_naive(&numbers_in(winners)?, &numbers_in(drawn)?))
+        })
+        .collect()
+}
+
+/// Reference for part one.
+pub fn points_naive(input: &str) -> MyResult<Answer> {
+    matches_of(input)?.iter().try_fold(0u64, |sum, matches| {
+        let points = match matches {
+            0 => 0,
+            count => checked(1u64.checked_shl((count - 1).try_into()?))?,
+        };
+        checked(sum.checked_add(points))
+    })
+}
+
+/// Reference for part two that processes every copy of every card one by one.
+pub fn total_cards_naive(input: &str) -> MyResult<Answer> {
+    const LIMIT: u64 = 100_000_000;
+    let matches = matches_of(input)?;
+    let mut pending: Vec<usize> = (0..matches.len()).collect();
+    let mut total = 0;
+    while let Some(i) = pending.pop() {
+        total += 1;
+        if total > LIMIT {
+            return Err(format!("more than {} cards to count one by one", LIMIT).into());
+        }
+        pending.extend(i + 1..cmp::min(i + 1 + matches[i], matches.len()));
+    }
+    Ok(total)
+}
+
+#[test]
+fn test_card_matches() {
+    let card = Card::from_str("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
+    assert_eq!(card.matches(), 4);
+    assert_eq!(card.points().unwrap(), 8);
+    let card = Card::from_str("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11").unwrap();
+    assert_eq!(card.matches(), 0);
+    assert_eq!(card.points().unwrap(), 0);
+    assert!(Card::from_str("Card +1: 41 | 41").is_err());
+    assert!(Card::from_str("Card -1: 41 | 41").is_err());
+}
+
+#[test]
+fn test_card_large_numbers() {
+    let card = Card::from_str("Card 1: 4294967295 7 200 | 1 200 4294967295").unwrap();
+    assert_eq!(card.matches(), 2);
+    assert_eq!(card.to_string(), "Card 1:  7 200 4294967295 |  1 200 4294967295");
+}
+
+#[cfg(test)]
+fn card_with_matches(id: usize, count: u32) -> String {
+    let numbers: Vec<String> = (1..=count).map(|n| n.to_string()).collect();
+    format!("Card {}: {} | {}", id, numbers.join(" "), numbers.join(" "))
+}
+
+#[test]
+fn test_card_points_overflow() {
+    let card = Card::from_str(&card_with_matches(1, 40)).unwrap();
+    assert_eq!(card.points().unwrap(), 1 << 39);
+    let card = Card::from_str(&card_with_matches(1, 65)).unwrap();
+    assert_eq!(card.points().unwrap_err().to_string(), "arithmetic overflow");
+}
+
+#[test]
+fn test_total_cards_overflow() {
+    // Every card wins a copy of all later cards, doubling the count each time.
+    let lines: Vec<String> = (1..=40).map(|id| card_with_matches(id, 41 - id as u32)).collect();
+    let cards: Vec<Card> = lines.iter().map(|l| Card::from_str(l).unwrap()).collect();
+    assert_eq!(total_cards(&cards).unwrap(), (1 << 40) - 1);
+    let lines: Vec<String> = (1..=70).map(|id| card_with_matches(id, 71 - id as u32)).collect();
+    let cards: Vec<Card> = lines.iter().map(|l| Card::from_str(l).unwrap()).collect();
+    assert_eq!(total_cards(&cards).unwrap_err().to_string(), "arithmetic overflow");
+}
+
+#[cfg(test)]
+fn cards_of(lines: &[&str]) -> MyResult<Vec<Card>> {
+    Day4::parse(&lines.join("\n"))
+}
+
+#[test]
+fn test_total_cards() {
+    let cards = cards_of(&[
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+        "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+        "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+        "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+        "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+        "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+    ])
+    .unwrap();
+    assert_eq!(total_cards(&cards).unwrap(), 30);
+}
+
+#[test]
+fn test_total_cards_wins_past_end() {
+    // Card 2 wins two copies but there is no card 3 or 4 to copy.
+    let cards = cards_of(&["Card 1: 1 2 | 1 3", "Card 2: 1 2 | 1 2"]).unwrap();
+    assert_eq!(total_cards(&cards).unwrap(), 3);
+}
+
+#[test]
+fn test_total_cards_bad_ids() {
+    let gap = cards_of(&["Card 1: 1 | 2", "Card 3: 1 | 2"]).unwrap();
+    assert_eq!(total_cards(&gap).unwrap_err().to_string(), "missing card 2, found card 3");
+    let duplicate = cards_of(&["Card 1: 1 | 2", "Card 1: 1 | 2"]).unwrap();
+    assert_eq!(total_cards(&duplicate).unwrap_err().to_string(), "duplicate card 1");
+    let order = cards_of(&["Card 2: 1 | 2", "Card 1: 1 | 2"]).unwrap();
+    assert_eq!(total_cards(&order).unwrap_err().to_string(), "missing card 1, found card 2");
+    let order = cards_of(&["Card 1: 1 | 2", "Card 2: 1 | 2", "Card 1: 1 | 2"]).unwrap();
+    assert_eq!(total_cards(&order).unwrap_err().to_string(), "card 1 out of order, expected 3");
+}
+
+/// Compares the number set against the old linear scan, through its bitset
+/// on the real input and through its hash set on the synthetic one, whose
+/// numbers go past [`SMALL`]; run with
+/// `cargo test --release bench_card_matches -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn bench_card_matches() {
+    use std::time::Instant;
+
+    fn bench(name: &str, lines: &[String], rounds: usize) {
+        let parsed: Vec<(Vec<u32>, Vec<u32>)> = lines
+            .iter()
+            .map(|line| {
+                let colon = line.find(':').unwrap();
+                let pipe = line.find('|').unwrap();
+                (
+                    numbers_in(&line[colon + 1..pipe]).unwrap(),
+                    numbers_in(&line[pipe + 1..]).unwrap(),
+                )
+            })
+            .collect();
+        let cards: Vec<Card> = lines.iter().map(|l| Card::from_str(l).unwrap()).collect();
+
+        let start = Instant::now();
+        let naive: usize = (0..rounds)
+            .map(|_| parsed.iter().map(|(w, d)| matches_naive(w, d)).sum::<usize>())
+            .sum();
+        let naive_time = start.elapsed();
+        let start = Instant::now();
+        let set: usize = (0..rounds)
+            .map(|_| cards.iter().map(Card::matches).sum::<usize>())
+            .sum();
+        let set_time = start.elapsed();
+
+        assert_eq!(naive, set);
+        println!(
+            "{}: naive {:?}, set {:?} ({:.1}x)",
+            name,
+            naive_time / rounds as u32,
+            set_time / rounds as u32,
+            naive_time.as_secs_f64() / set_time.as_secs_f64()
+        );
+    }
+
+    let input: Vec<String> = std::fs::read_to_string("./input/2023/input4.txt")
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+    bench("input4.txt, bitset", &input, 1000);
+
+    let mut seed = 0x2545f4914f6cdd1du64;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 10_000) as u32
+    };
+    let synthetic: Vec<String> = (1..=50)
+        .map(|id| {
+            let winners: Vec<String> = (0..1_000).map(|_| next().to_string()).collect();
+            let drawn: Vec<String> = (0..2_000).map(|_| next().to_string()).collect();
+            format!("Card {}: {} | {}", id, winners.join(" "), drawn.join(" "))
+        })
+        .collect();
+    bench("synthetic 1000x2000, hash set", &synthetic, 5);
+}
@@ -0,0 +1,568 @@
+use crate::solver::{Answer, Day};
+use crate::types::{parse_uint, MyResult};
+use std::cmp;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum Category {
+    Seed,
+    Soil,
+    Fertilizer,
+    Water,
+    Light,
+    Temperature,
+    Humidity,
+    Location,
+}
+impl Category {
+    fn of(value: &str) -> MyResult<Category> {
+        match value {
+            "seed" => Ok(Category::Seed),
+            "soil" => Ok(Category::Soil),
+            "fertilizer" => Ok(Category::Fertilizer),
+            "water" => Ok(Category::Water),
+            "light" => Ok(Category::Light),
+            "temperature" => Ok(Category::Temperature),
+            "humidity" => Ok(Category::Humidity),
+            "location" => Ok(Category::Location),
+            _ => Err(format!("category '{}'?", value).into()),
+        }
+    }
+    /// The category a map from `self` leads to.
+    pub fn next(&self) -> Category {
+        match self {
+            Category::Seed => Category::Soil,
+            Category::Soil => Category::Fertilizer,
+            Category::Fertilizer => Category::Water,
+            Category::Water => Category::Light,
+            Category::Light => Category::Temperature,
+            Category::Temperature => Category::Humidity,
+            Category::Humidity | Category::Location => Category::Location,
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Category::Seed => "seed",
+            Category::Soil => "soil",
+            Category::Fertilizer => "fertilizer",
+            Category::Water => "water",
+            Category::Light => "light",
+            Category::Temperature => "temperature",
+            Category::Humidity => "humidity",
+            Category::Location => "location",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Range {
+    pub from: i64,
+    pub size: i64,
+}
+
+impl Range {
+    pub fn to(&self) -> i64 {
+        self.from + self.size - 1
+    }
+    pub fn move_by(&self, value: i64) -> Range {
+        Range {
+            from: self.from + value,
+            size: self.size,
+        }
+    }
+    pub fn project(&self, dest: i64) -> Range {
+        Range {
+            from: dest,
+            size: self.size,
+        }
+    }
+    pub fn intersect(&self, range: &Range) -> Option<Range> {
+        Range::start_end(
+            cmp::max(self.from, range.from),
+            cmp::min(self.to(), range.to()),
+        )
+    }
+    pub fn prefix(&self, range: &Range) -> Option<Range> {
+        Range::start_end(self.from, cmp::min(self.to(), range.from - 1))
+    }
+    pub fn suffix(&self, range: &Range) -> Option<Range> {
+        Range::start_end(cmp::max(self.from, range.to() + 1), self.to())
+    }
+    pub fn substract(&self, range: &Range) -> Vec<Range> {
+        if self.intersect(range).is_some() {
+            [self.prefix(range), self.suffix(range)]
+                .into_iter()
+                .flatten()
+                .collect()
+        } else {
+            vec![*self]
+        }
+    }
+    pub fn substract_all<'a>(&self, ranges: impl IntoIterator<Item = &'a Range>) -> Vec<Range> {
+        ranges.into_iter().fold(vec![*self], |rest, range| {
+            rest.iter().flat_map(|r| r.substract(range)).collect()
+        })
+    }
+    pub fn start_end(start: i64, end: i64) -> Option<Range> {
+        (start <= end).then_some(Range {
+            from: start,
+            size: end + 1 - start,
+        })
+    }
+    pub fn from_size(from: i64, size: i64) -> Option<Range> {
+        (size > 0).then_some(Range { from, size })
+    }
+    pub fn tr_val(&self, value: i64, dest: i64) -> Option<i64> {
+        (self.from <= value && value <= self.to()).then(|| value + dest - self.from)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Translation {
+    pub range: Range,
+    pub dest: i64,
+}
+
+impl fmt::Display for Translation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.dest, self.range.from, self.range.size)
+    }
+}
+
+impl Translation {
+    fn from_str(vec: &[&str]) -> MyResult<Translation> {
+        let [dest, from, size] = vec else {
+            return Err(format!("'{}'?", vec.join(" ")).into());
+        };
+        let (from, size, dest) = (number(from)?, number(size)?, number(dest)?);
+        Ok(Translation {
+            range: Range::from_size(from, size).ok_or("range?")?,
+            dest,
+        })
+    }
+    pub fn tr_val(&self, val: i64) -> Option<i64> {
+        self.range.tr_val(val, self.dest)
+    }
+    /// The part of `self` whose destination falls in `other`, translated by both.
+    pub fn join(&self, other: &Translation) -> Option<Translation> {
+        let project = self.range.project(self.dest);
+        project.intersect(&other.range).map(|inter| Translation {
+            range: inter.move_by(self.range.from - project.from),
+            dest: other.dest + inter.from - other.range.from,
+        })
+    }
+}
+
+#[test]
+fn test_translation_join() {
+    let that = Translation {
+        range: Range { from: 1, size: 8 },
+        dest: 8,
+    };
+    let other = Translation {
+        range: Range { from: 12, size: 40 },
+        dest: 100,
+    };
+    let result = that.join(&other);
+    assert_eq!(
+        result,
+        Some(Translation {
+            range: Range { from: 5, size: 4 },
+            dest: 100
+        })
+    );
+}
+
+pub struct TranslationMap<'a> {
+    seed: &'a TranslationCategory,
+    soil: &'a TranslationCategory,
+    fertilizer: &'a TranslationCategory,
+    water: &'a TranslationCategory,
+    light: &'a TranslationCategory,
+    temperature: &'a TranslationCategory,
+    humidity: &'a TranslationCategory,
+}
+
+impl TranslationMap<'_> {
+    fn filter(
+        input_maps: &[TranslationCategory],
+        category: Category,
+    ) -> MyResult<&TranslationCategory> {
+        Ok(input_maps
+            .iter()
+            .find(|input_map| input_map.category.eq(&category))
+            .ok_or("seed?")?)
+    }
+    pub fn of(categories: &[TranslationCategory]) -> MyResult<TranslationMap<'_>> {
+        Ok(TranslationMap {
+            seed: TranslationMap::filter(categories, Category::Seed)?,
+            soil: TranslationMap::filter(categories, Category::Soil)?,
+            fertilizer: TranslationMap::filter(categories, Category::Fertilizer)?,
+            water: TranslationMap::filter(categories, Category::Water)?,
+            light: TranslationMap::filter(categories, Category::Light)?,
+            temperature: TranslationMap::filter(categories, Category::Temperature)?,
+            humidity: TranslationMap::filter(categories, Category::Humidity)?,
+        })
+    }
+    pub fn tr(&self, seed: i64) -> i64 {
+        let soil = self.seed.tr(seed);
+        let fertilizer = self.soil.tr(soil);
+        let water = self.fertilizer.tr(fertilizer);
+        let light = self.water.tr(water);
+        let temperature = self.light.tr(light);
+        let humidity = self.temperature.tr(temperature);
+        self.humidity.tr(humidity)
+    }
+    pub fn tr_ranges(&self, seeds: &[Range]) -> Vec<Range> {
+        [self.soil, self.fertilizer, self.water, self.light, self.temperature, self.humidity]
+            .iter()
+            .fold(self.seed.tr_ranges(seeds), |ranges, category| category.tr_ranges(&ranges))
+    }
+    /// All categories composed into a single seed to location translation.
+    pub fn translations(&self) -> Translations {
+        self.seed
+            .translations
+            .join(&self.soil.translations)
+            .join(&self.fertilizer.translations)
+            .join(&self.water.translations)
+            .join(&self.light.translations)
+            .join(&self.temperature.translations)
+            .join(&self.humidity.translations)
+    }
+}
+
+/// Values not covered by any translation map to themselves.
+#[derive(Debug, PartialEq)]
+pub struct Translations {
+    pub translations: Vec<Translation>,
+}
+
+impl Translations {
+    pub fn join_one(&self, other: &Translation) -> Vec<Translation> {
+        self.translations
+            .iter()
+            .filter_map(|that| that.join(other))
+            .collect()
+    }
+    /// Composes `self` followed by `other`, keeping the identity parts of both.
+    pub fn join(&self, other: &Translations) -> Translations {
+        let other_ranges: Vec<Range> = other.translations.iter().map(|o| o.range).collect();
+        let self_ranges: Vec<Range> = self.translations.iter().map(|t| t.range).collect();
+        let joined = other
+            .translations
+            .iter()
+            .flat_map(|other| self.join_one(other));
+        let self_only = self.translations.iter().flat_map(|that| {
+            let project = that.range.project(that.dest);
+            project
+                .substract_all(&other_ranges)
+                .into_iter()
+                .map(move |rest| Translation {
+                    range: rest.move_by(that.range.from - that.dest),
+                    dest: rest.from,
+                })
+        });
+        let other_only = other.translations.iter().flat_map(|other| {
+            other
+                .range
+                .substract_all(&self_ranges)
+                .into_iter()
+                .map(move |rest| Translation {
+                    range: rest,
+                    dest: other.dest + rest.from - other.range.from,
+                })
+        });
+        let translations: Vec<Translation> = joined.chain(self_only).chain(other_only).collect();
+        crate::trace!(
+            Trace,
+            "2023/day5",
+            "join {} with {} translations: {}",
+            self.translations.len(),
+            other.translations.len(),
+            translations.len()
+        );
+        Translations { translations }
+    }
+    pub fn tr_val(&self, val: i64) -> Option<i64> {
+        self.translations.iter().find_map(|pair| pair.tr_val(val))
+    }
+    /// Smallest value any number in `range` translates to.
+    pub fn tr_min(&self, range: &Range) -> Option<i64> {
+        let translated = self.translations.iter().filter_map(|t| {
+            range
+                .intersect(&t.range)
+                .and_then(|inter| t.tr_val(inter.from))
+        });
+        let ranges: Vec<Range> = self.translations.iter().map(|t| t.range).collect();
+        let untranslated = range.substract_all(&ranges).into_iter().map(|r| r.from);
+        translated.chain(untranslated).min()
+    }
+}
+
+#[test]
+fn test_translations_join_one() {
+    let that = Translations {
+        translations: vec![
+            Translation {
+                range: Range { from: 1, size: 4 },
+                dest: 100,
+            },
+            Translation {
+                range: Range { from: 10, size: 4 },
+                dest: 200,
+            },
+        ],
+    };
+    let other = Translation {
+        range: Range {
+            from: 102,
+            size: 100,
+        },
+        dest: 1000,
+    };
+    let result = that.join_one(&other);
+    assert_eq!(
+        result,
+        [
+            Translation {
+                range: Range { from: 3, size: 2 },
+                dest: 1000
+            },
+            // 10 goes to 200, which is 98 past the start of `other`.
+            Translation {
+                range: Range { from: 10, size: 2 },
+                dest: 1098
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_translations_join() {
+    let almanac = Day5::parse(&crate::examples::demo(2023, 5).unwrap()).unwrap();
+    let translation_map = TranslationMap::of(&almanac.categories).unwrap();
+    let translations = translation_map.translations();
+    for seed in 0..120 {
+        assert_eq!(
+            translations.tr_val(seed).unwrap_or(seed),
+            translation_map.tr(seed),
+            "seed {}",
+            seed
+        );
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TranslationCategory {
+    pub category: Category,
+    pub translations: Translations,
+}
+
+impl fmt::Display for TranslationCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}-to-{} map:", self.category, self.category.next())?;
+        for translation in &self.translations.translations {
+            writeln!(f, "{}", translation)?;
+        }
+        Ok(())
+    }
+}
+
+impl TranslationCategory {
+    pub fn read<'a>(
+        lines: &mut impl Iterator<Item = &'a str>,
+    ) -> MyResult<Option<TranslationCategory>> {
+        if let Some(title_line) = lines.next() {
+            let from_sep = title_line.find("-to-").ok_or("'-to-' ?")?;
+            let from = &title_line[0..from_sep];
+            let mut translations: Vec<Translation> = vec![];
+            for line in lines.by_ref() {
+                if line.is_empty() {
+                    break;
+                }
+                let parts: Vec<&str> = line.splitn(3, ' ').collect();
+                translations.push(Translation::from_str(&parts)?);
+            }
+            Ok(Some(TranslationCategory {
+                category: Category::of(from)?,
+                translations: Translations { translations },
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+    pub fn tr(&self, val: i64) -> i64 {
+        self.translations.tr_val(val).unwrap_or(val)
+    }
+    /// Translates whole ranges, splitting them where translations start or end.
+    pub fn tr_ranges(&self, ranges: &[Range]) -> Vec<Range> {
+        let translations = &self.translations.translations;
+        let sources: Vec<Range> = translations.iter().map(|t| t.range).collect();
+        ranges
+            .iter()
+            .flat_map(|range| {
+                let translated = translations.iter().filter_map(|t| {
+                    range
+                        .intersect(&t.range)
+                        .map(|inter| inter.move_by(t.dest - t.range.from))
+                });
+                translated.chain(range.substract_all(&sources))
+            })
+            .collect()
+    }
+}
+
+/// Numbers in an almanac stay below this, so that sums of a few of them
+/// cannot overflow.
+pub const LIMIT: i64 = 1 << 48;
+
+/// Parses a number below [`LIMIT`].
+fn number(text: &str) -> MyResult<i64> {
+    let value: i64 = match text.strip_prefix('-') {
+        Some(digits) => -parse_uint::<i64>(digits.as_bytes())?,
+        None => parse_uint(text.as_bytes())?,
+    };
+    match value.unsigned_abs() < LIMIT as u64 {
+        true => Ok(value),
+        false => Err(format!("number {} too large", value).into()),
+    }
+}
+
+#[test]
+fn test_number() {
+    assert_eq!(number("-281474976710655").unwrap(), 1 - (1 << 48));
+    assert!(number("-281474976710656").is_err());
+    assert!(number("-9223372036854775808").is_err());
+}
+
+pub fn seeds(line: &str) -> MyResult<Vec<i64>> {
+    let space = line.find(' ').ok_or("space?")?;
+    let seed_str = &line[space + 1..];
+    seed_str.split(' ').map(number).collect()
+}
+
+pub fn seed_pairs(seeds: &[i64]) -> MyResult<Vec<Range>> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err("seed pair?".into());
+    }
+    seeds
+        .chunks_exact(2)
+        .map(|pair| Ok(Range::from_size(pair[0], pair[1]).ok_or("size?")?))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub categories: Vec<TranslationCategory>,
+}
+
+impl fmt::Display for Almanac {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "seeds:")?;
+        for seed in &self.seeds {
+            write!(f, " {}", seed)?;
+        }
+        writeln!(f)?;
+        // The blank line after the seeds is there even without any maps.
+        let categories: Vec<String> = self.categories.iter().map(|c| c.to_string()).collect();
+        write!(f, "\n{}", categories.join("\n"))
+    }
+}
+
+pub struct Day5;
+
+impl Day for Day5 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 5;
+    type Model<'a> = Almanac;
+
+    fn parse(input: &str) -> MyResult<Almanac> {
+        let mut lines = input.lines();
+
+        let line = lines.next().ok_or("first?")?;
+        let seeds = seeds(line)?;
+        lines.next().ok_or("next?")?;
+        let mut categories: Vec<TranslationCategory> = vec![];
+        while let Some(map) = TranslationCategory::read(&mut lines)? {
+            categories.push(map);
+        }
+        Ok(Almanac { seeds, categories })
+    }
+
+    fn print(almanac: &Almanac) -> String {
+        almanac.to_string()
+    }
+
+    fn p1(almanac: &Almanac) -> MyResult<Answer> {
+        let translation_map = TranslationMap::of(&almanac.categories)?;
+        let min = almanac
+            .seeds
+            .iter()
+            .map(|val| {
+                let location = translation_map.tr(val.to_owned());
+                crate::trace!(Trace, "2023/day5", "seed {} -> location {}", val, location);
+                location
+            })
+            .min()
+            .ok_or("min?")?;
+        Ok(min.try_into()?)
+    }
+
+    fn p2(almanac: &Almanac) -> MyResult<Answer> {
+        let translations = TranslationMap::of(&almanac.categories)?.translations();
+        crate::trace!(Debug, "2023/day5", "{} composed translations", translations.translations.len());
+        let min = seed_pairs(&almanac.seeds)?
+            .iter()
+            .filter_map(|range| {
+                let min = translations.tr_min(range);
+                crate::trace!(Trace, "2023/day5", "seeds {:?} -> lowest location {:?}", range, min);
+                min
+            })
+            .min()
+            .ok_or("min?")?;
+        Ok(min.try_into()?)
+    }
+}
+
+/// Reference for part one through the composed translations.
+pub fn lowest_location_composed(almanac: &Almanac) -> MyResult<Answer> {
+    let translations = TranslationMap::of(&almanac.categories)?.translations();
+    let min = almanac
+        .seeds
+        .iter()
+        .map(|seed| translations.tr_val(*seed).unwrap_or(*seed))
+        .min()
+        .ok_or("min?")?;
+    Ok(min.try_into()?)
+}
+
+/// Reference for part two that carries the seed ranges through each map in turn.
+pub fn lowest_location_by_ranges(almanac: &Almanac) -> MyResult<Answer> {
+    let translation_map = TranslationMap::of(&almanac.categories)?;
+    let min = translation_map
+        .tr_ranges(&seed_pairs(&almanac.seeds)?)
+        .iter()
+        .map(|range| range.from)
+        .min()
+        .ok_or("min?")?;
+    Ok(min.try_into()?)
+}
+
+#[test]
+fn test_demo() {
+    let almanac = Day5::parse(&crate::examples::demo(2023, 5).unwrap()).unwrap();
+    assert_eq!(Day5::p1(&almanac).unwrap(), 35);
+    assert_eq!(Day5::p2(&almanac).unwrap(), 46);
+}
+
+#[test]
+fn test_print_almanac() {
+    let demo = crate::examples::demo(2023, 5).unwrap();
+    let almanac = Day5::parse(&demo).unwrap();
+    assert_eq!(Day5::print(&almanac), demo);
+}
@@ -0,0 +1,50 @@
+use super::day5::Day5;
+use super::{day1, day4, day5};
+use crate::diff::Reference;
+use crate::solver::{Day, Part};
+
+/// The references of 2023.
+pub static REFERENCES: &[Reference] = &[
+    Reference {
+        year: 2023,
+        day: 1,
+        part: Part::One,
+        name: "scan",
+        solve: |input| day1::calibration_scan(input, false),
+    },
+    Reference {
+        year: 2023,
+        day: 1,
+        part: Part::Two,
+        name: "scan",
+        solve: |input| day1::calibration_scan(input, true),
+    },
+    Reference {
+        year: 2023,
+        day: 4,
+        part: Part::One,
+        name: "naive",
+        solve: day4::points_naive,
+    },
+    Reference {
+        year: 2023,
+        day: 4,
+        part: Part::Two,
+        name: "naive",
+        solve: day4::total_cards_naive,
+    },
+    Reference {
+        year: 2023,
+        day: 5,
+        part: Part::One,
+        name: "composed",
+        solve: |input| day5::lowest_location_composed(&Day5::parse(input)?),
+    },
+    Reference {
+        year: 2023,
+        day: 5,
+        part: Part::Two,
+        name: "ranges",
+        solve: |input| day5::lowest_location_by_ranges(&Day5::parse(input)?),
+    },
+];
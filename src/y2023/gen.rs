@@ -0,0 +1,170 @@
+use super::day5;
+use crate::gen::{Rng, Size};
+use std::collections::HashSet;
+
+/// A random input for `day` that its solver accepts.
+pub fn input(day: u32, rng: &mut Rng, size: &Size) -> Option<String> {
+    match day {
+        1 => Some(day1(rng, size)),
+        2 => Some(day2(rng, size)),
+        3 => Some(day3(rng, size)),
+        4 => Some(day4(rng, size)),
+        5 => Some(day5(rng, size)),
+        _ => None,
+    }
+}
+
+const DIGIT_WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Letters with digits and digit words mixed in; every line has a digit.
+pub fn day1(rng: &mut Rng, size: &Size) -> String {
+    let mut input = String::new();
+    for _ in 0..size.lines {
+        let mut line = String::new();
+        while line.len() < size.width.max(1) {
+            match rng.below(10) {
+                0..=1 => line.push(char::from(b'1' + rng.below(9) as u8)),
+                2 => line += *rng.pick(&DIGIT_WORDS),
+                _ => line.push(char::from(b'a' + rng.below(26) as u8)),
+            }
+        }
+        if !line.bytes().any(|b| b.is_ascii_digit()) {
+            let at = rng.below(line.len() as u64 + 1) as usize;
+            line.insert(at, char::from(b'1' + rng.below(9) as u8));
+        }
+        input += &line;
+        input.push('\n');
+    }
+    input
+}
+
+pub fn day2(rng: &mut Rng, size: &Size) -> String {
+    let max = size.max.unwrap_or(20).clamp(1, u32::MAX.into());
+    let mut input = String::new();
+    for id in 1..=size.lines {
+        let mut rounds = vec![];
+        for _ in 0..rng.between(1, 6) {
+            let mut colours = vec![];
+            for colour in ["red", "green", "blue"] {
+                if rng.chance(70) {
+                    colours.push(format!("{} {}", rng.between(1, max), colour));
+                }
+            }
+            if colours.is_empty() {
+                colours.push(format!("{} red", rng.between(1, max)));
+            }
+            rounds.push(colours.join(", "));
+        }
+        input += &format!("Game {}: {}\n", id, rounds.join("; "));
+    }
+    input
+}
+
+/// A grid of dots with numbers of up to three digits and scattered symbols.
+pub fn day3(rng: &mut Rng, size: &Size) -> String {
+    let width = size.width.max(1);
+    let mut input = String::new();
+    for _ in 0..size.lines {
+        let mut line = String::new();
+        while line.len() < width {
+            match rng.below(10) {
+                0..=1 => {
+                    let digits = rng.between(1, 3).min((width - line.len()) as u64) as u32;
+                    line += &rng.between(10u64.pow(digits - 1), 10u64.pow(digits) - 1).to_string();
+                    if line.len() < width {
+                        line.push('.');
+                    }
+                }
+                2 => line.push(*rng.pick(&['*', '*', '#', '+', '$', '/', '=', '%', '@', '&', '-'])),
+                _ => line.push('.'),
+            }
+        }
+        input += &line;
+        input.push('\n');
+    }
+    input
+}
+
+/// Distinct numbers in `1..=max` in random order, fewer if there are not
+/// enough of them. Floyd's algorithm picks them without listing `1..=max`.
+fn distinct(rng: &mut Rng, count: usize, max: u64) -> Vec<u64> {
+    let count = (count as u64).min(max);
+    let mut picked = HashSet::new();
+    let mut numbers = vec![];
+    for j in max - count + 1..=max {
+        let n = rng.between(1, j);
+        let n = if picked.insert(n) { n } else { j };
+        picked.insert(n);
+        numbers.push(n);
+    }
+    rng.shuffle(&mut numbers);
+    numbers
+}
+
+pub fn day4(rng: &mut Rng, size: &Size) -> String {
+    // About one match per card on average; more would make the copies of
+    // part two grow exponentially with the number of cards.
+    let max = size.winners.saturating_mul(size.drawn).max(size.winners.saturating_add(size.drawn));
+    let max = (max as u64).min(u32::MAX.into());
+    let width = max.to_string().len();
+    let line = |numbers: &[u64]| -> String {
+        numbers.iter().map(|n| format!("{:>width$}", n, width = width)).collect::<Vec<_>>().join(" ")
+    };
+    let mut input = String::new();
+    for id in 1..=size.lines {
+        let winners = distinct(rng, size.winners, max);
+        let drawn = distinct(rng, size.drawn, max);
+        input += &format!("Card {:>4}: {} | {}\n", id, line(&winners), line(&drawn));
+    }
+    input
+}
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+/// Seven maps, each cutting `0..max` into pieces that it lays out again in
+/// a shuffled order, like the real maps; a few pieces are left out and so
+/// map to themselves.
+pub fn day5(rng: &mut Rng, size: &Size) -> String {
+    let max = size.max.unwrap_or(1 << 32).clamp(2, day5::LIMIT as u64 - 1);
+    let mut input = String::from("seeds:");
+    for _ in 0..size.lines.max(1) {
+        let from = rng.below(max - 1);
+        input += &format!(" {} {}", from, rng.between(1, max - from - 1));
+    }
+    input += "\n";
+    for pair in CATEGORIES.windows(2) {
+        input += &format!("\n{}-to-{} map:\n", pair[0], pair[1]);
+        let mut cuts: Vec<u64> = (0..size.ranges).map(|_| rng.between(1, max - 1)).collect();
+        cuts.extend([0, max]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        let pieces: Vec<(u64, u64)> = cuts.windows(2).map(|cut| (cut[0], cut[1] - cut[0])).collect();
+        let mut order: Vec<usize> = (0..pieces.len()).collect();
+        rng.shuffle(&mut order);
+        let mut dest = 0;
+        for &i in &order {
+            let (from, length) = pieces[i];
+            if rng.chance(85) {
+                input += &format!("{} {} {}\n", dest, from, length);
+            }
+            dest += length;
+        }
+    }
+    input
+}
+
+#[test]
+fn test_day4_large_sizes() {
+    let numbers = distinct(&mut Rng::new(0), 1000, 1000);
+    assert_eq!(numbers.iter().collect::<HashSet<_>>().len(), 1000);
+    assert!(numbers.iter().all(|n| (1..=1000).contains(n)));
+    assert_eq!(distinct(&mut Rng::new(0), 5, 3).len(), 3);
+
+    // Picking from 10^8 numbers must not list them all.
+    let size = Size { lines: 1, winners: 10_000, drawn: 10_000, ..Size::default() };
+    let input = day4(&mut Rng::new(0), &size);
+    assert_eq!(input.split_whitespace().filter(|word| word.parse::<u64>().is_ok()).count(), 20_000);
+    // Sizes whose product overflows do not panic working out the range.
+    let size = Size { lines: 0, winners: usize::MAX, drawn: usize::MAX, ..Size::default() };
+    assert_eq!(day4(&mut Rng::new(0), &size), "");
+}
@@ -0,0 +1,30 @@
+pub mod anonymise;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod diff;
+pub mod gen;
+pub mod repl;
+
+use crate::solver::Solver;
+use crate::Year;
+
+/// Every solved day of 2023, in day order.
+pub static DAYS: &[&dyn Solver] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+];
+
+/// 2023 with its generators, anonymisers, REPL commands and references.
+pub static YEAR: Year = Year {
+    generate: gen::input,
+    anonymise: anonymise::anonymise,
+    session: repl::session,
+    references: diff::REFERENCES,
+    ..Year::new(2023, DAYS)
+};
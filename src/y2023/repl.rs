@@ -0,0 +1,175 @@
+use super::day1::{self, Day1};
+use super::day2::{Day2, Game};
+use super::day3::{Day3, Line};
+use super::day4::{Card, Day4};
+use super::day5::{Almanac, Category, Day5, Range, TranslationMap};
+use crate::repl::{arg, command, explorer, nth, unknown, Command, Explore};
+use crate::types::MyResult;
+
+/// Parses `input` of `day` for a session.
+pub fn session(day: u32, input: &str) -> Option<MyResult<Box<dyn Explore + '_>>> {
+    Some(match day {
+        1 => explorer::<Day1>(input, DAY1, |lines, command, args| day1(lines, command, args)),
+        2 => explorer::<Day2>(input, DAY2, |games, command, args| day2(games, command, args)),
+        3 => explorer::<Day3>(input, DAY3, |lines, command, args| day3(lines, command, args)),
+        4 => explorer::<Day4>(input, DAY4, |cards, command, args| day4(cards, command, args)),
+        5 => explorer::<Day5>(input, DAY5, day5),
+        _ => return None,
+    })
+}
+
+const DAY1: &[Command] = &[
+    command("line", "N", "shows line N"),
+    command("calibration", "N", "the calibration value of line N in both parts"),
+];
+
+fn day1(lines: &[&str], command: &str, args: &[&str]) -> MyResult<String> {
+    let line = || nth(lines, arg(args, 0, "line")?, "line");
+    match command {
+        "line" => Ok(line()?.to_string()),
+        "calibration" => {
+            let line = line()?;
+            let value = |words| day1::calibration_scan(line, words).map_or_else(|e| e.to_string(), |v| v.to_string());
+            Ok(format!("p1 {}, p2 {}", value(false), value(true)))
+        }
+        command => unknown(command),
+    }
+}
+
+const DAY2: &[Command] = &[
+    command("game", "ID", "shows game ID, whether it is possible, its fewest cubes and their power"),
+    command("possible", "", "lists the ids of the possible games"),
+];
+
+fn day2(games: &[Game], command: &str, args: &[&str]) -> MyResult<String> {
+    match command {
+        "game" => {
+            let id: u64 = arg(args, 0, "id")?;
+            let game = games.iter().find(|game| game.id == id).ok_or_else(|| format!("no game {}", id))?;
+            let fewest = game.min_round();
+            Ok(format!(
+                "{}\npossible: {}\nfewest: {}\npower: {}",
+                game,
+                game.is_possible(),
+                fewest,
+                fewest.power()?
+            ))
+        }
+        "possible" => {
+            let ids: Vec<String> = games.iter().filter(|game| game.is_possible()).map(|game| game.id.to_string()).collect();
+            Ok(format!("{} of {}: {}", ids.len(), games.len(), ids.join(" ")))
+        }
+        command => unknown(command),
+    }
+}
+
+const DAY3: &[Command] = &[command("row", "N", "shows row N with its part numbers, symbols and gears")];
+
+fn day3(lines: &[Line], command: &str, args: &[&str]) -> MyResult<String> {
+    match command {
+        "row" => {
+            let line = nth(lines, arg(args, 0, "row")?, "row")?;
+            let parts: Vec<String> = line.parts.iter().map(|part| format!("{}@{}", part.value, part.start)).collect();
+            let columns = |columns: &[i32]| columns.iter().map(i32::to_string).collect::<Vec<_>>().join(" ");
+            Ok(format!(
+                "{}\nnumbers: {}\nsymbols at: {}\ngears at: {}",
+                line,
+                parts.join(" "),
+                columns(&line.symbols),
+                columns(&line.gears)
+            ))
+        }
+        command => unknown(command),
+    }
+}
+
+const DAY4: &[Command] = &[command("card", "ID [matches|points]", "shows card ID, or only its matches or points")];
+
+fn day4(cards: &[Card], command: &str, args: &[&str]) -> MyResult<String> {
+    match command {
+        "card" => {
+            let id: usize = arg(args, 0, "id")?;
+            let card = cards.iter().find(|card| card.id == id).ok_or_else(|| format!("no card {}", id))?;
+            match args.get(1) {
+                None => Ok(format!("{}\nmatches: {}\npoints: {}", card, card.matches(), card.points()?)),
+                Some(&"matches") => Ok(card.matches().to_string()),
+                Some(&"points") => Ok(card.points()?.to_string()),
+                Some(what) => Err(format!("'{}'? matches or points", what).into()),
+            }
+        }
+        command => unknown(command),
+    }
+}
+
+const DAY5: &[Command] = &[
+    command("seeds", "", "lists the seeds"),
+    command("maps", "", "lists the maps and how many translations each has"),
+    command("map", "CATEGORY", "shows the map from CATEGORY, such as seed or water"),
+    command("tr", "SEED", "the location SEED ends up at"),
+    command("trace", "SEED", "every value SEED goes through on its way to a location"),
+    command("split", "START SIZE", "how the range of seeds is split by each map"),
+];
+
+/// Each category from soil to location with the value `seed` has there.
+fn trace(almanac: &Almanac, seed: i64) -> MyResult<Vec<(Category, i64)>> {
+    let mut steps = vec![];
+    let (mut category, mut value) = (Category::Seed, seed);
+    while category != Category::Location {
+        let map = almanac
+            .categories
+            .iter()
+            .find(|map| map.category == category)
+            .ok_or_else(|| format!("no map from {}", category))?;
+        value = map.tr(value);
+        steps.push((category.next(), value));
+        category = category.next();
+    }
+    Ok(steps)
+}
+
+fn day5(almanac: &Almanac, command: &str, args: &[&str]) -> MyResult<String> {
+    let maps = || almanac.categories.iter();
+    match command {
+        "seeds" => Ok(almanac.seeds.iter().map(i64::to_string).collect::<Vec<_>>().join(" ")),
+        "maps" => Ok(maps()
+            .map(|map| {
+                let count = map.translations.translations.len();
+                format!("{}-to-{}: {} translations", map.category, map.category.next(), count)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")),
+        "map" => {
+            let name = args.first().ok_or("missing category")?;
+            let from = name.split("-to-").next().unwrap_or_default();
+            let map = maps().find(|map| map.category.to_string() == from).ok_or_else(|| format!("no map from '{}'", name))?;
+            Ok(map.to_string().trim_end().to_string())
+        }
+        "tr" => Ok(TranslationMap::of(&almanac.categories)?.tr(arg(args, 0, "seed")?).to_string()),
+        "trace" => {
+            let seed = arg(args, 0, "seed")?;
+            let steps = trace(almanac, seed)?;
+            let path: Vec<String> = steps.iter().map(|(category, value)| format!("{} {}", category, value)).collect();
+            Ok(format!("seed {} -> {}", seed, path.join(" -> ")))
+        }
+        "split" => {
+            let range = Range::from_size(arg(args, 0, "start")?, arg(args, 1, "size")?).ok_or("empty range")?;
+            let mut ranges = vec![range];
+            let mut lines = vec![];
+            let mut category = Category::Seed;
+            while category != Category::Location {
+                let map = maps().find(|map| map.category == category).ok_or_else(|| format!("no map from {}", category))?;
+                let before = ranges.len();
+                ranges = map.tr_ranges(&ranges);
+                let split = match ranges.len() > before {
+                    true => " (split)",
+                    false => "",
+                };
+                let shown: Vec<String> = ranges.iter().map(|range| format!("{}..={}", range.from, range.to())).collect();
+                lines.push(format!("{}-to-{}: {}{}", category, category.next(), shown.join(" "), split));
+                category = category.next();
+            }
+            Ok(lines.join("\n"))
+        }
+        command => unknown(command),
+    }
+}
@@ -0,0 +1,109 @@
+use rust_advent::gen::{self, Size};
+use rust_advent::memory::{self, Counting, Usage};
+use rust_advent::solver::Day;
+use rust_advent::types::MyResult;
+use rust_advent::y2023::day1::Day1;
+use rust_advent::y2023::day2::{Day2, Game};
+use rust_advent::y2023::day3::{Day3, Line};
+use rust_advent::y2023::day4::{Card, Day4};
+use rust_advent::y2023::day5::{self, Day5, TranslationCategory};
+use rust_advent::y2023::DAYS;
+use std::io::{BufRead, BufReader};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, Duration) {
+    let start = Instant::now();
+    let (result, usage) = memory::measure(f);
+    (result, usage.unwrap().allocations as usize, start.elapsed())
+}
+
+/// Reading line by line into owned strings, as the solvers used to, before
+/// handing the text to the parser.
+fn line_by_line(input: &str) -> Vec<String> {
+    BufReader::new(input.as_bytes()).lines().collect::<Result<_, _>>().unwrap()
+}
+
+/// Parses `day` the old way: line by line into owned strings, then each line
+/// on its own, with day 1 keeping the strings as its model.
+fn parse_line_by_line(day: u32, input: &str) -> MyResult<()> {
+    let lines = line_by_line(input);
+    match day {
+        1 => drop(lines),
+        2 => drop(lines.iter().map(|line| Game::from_str(line)).collect::<MyResult<Vec<_>>>()?),
+        3 => drop(lines.iter().map(|line| Line::from_str(line)).collect::<MyResult<Vec<_>>>()?),
+        4 => drop(lines.iter().map(|line| Card::from_str(line)).collect::<MyResult<Vec<_>>>()?),
+        5 => {
+            let mut lines = lines.iter().map(String::as_str);
+            let seeds = day5::seeds(lines.next().ok_or("first?")?)?;
+            lines.next().ok_or("next?")?;
+            let mut categories = vec![];
+            while let Some(map) = TranslationCategory::read(&mut lines)? {
+                categories.push(map);
+            }
+            drop((seeds, categories));
+        }
+        day => return Err(format!("no line by line parser for day {}", day).into()),
+    }
+    Ok(())
+}
+
+/// Parses `day` the new way, from the whole input.
+fn parse_whole(day: u32, input: &str) -> MyResult<()> {
+    match day {
+        1 => drop(Day1::parse(input)?),
+        2 => drop(Day2::parse(input)?),
+        3 => drop(Day3::parse(input)?),
+        4 => drop(Day4::parse(input)?),
+        5 => drop(Day5::parse(input)?),
+        day => return Err(format!("no parser for day {}", day).into()),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_whole_input_parsing_allocates_less() {
+    let size = Size { lines: 10_000, width: 140, ranges: 1_000, ..Size::default() };
+    println!("{:<5} {:>8} {:>10} {:>16} {:>10}", "day", "allocs", "time", "line by line", "time");
+    for solver in DAYS {
+        let day = solver.day();
+        let input = gen::input(2023, day, 0, &size).unwrap();
+        let lines = input.lines().count();
+        let (old, owned, old_time) = measure(|| parse_line_by_line(day, &input));
+        let (new, borrowed, time) = measure(|| parse_whole(day, &input));
+        old.unwrap();
+        new.unwrap();
+        println!("day{:<2} {:>8} {:>10.1?} {:>16} {:>10.1?}", day, borrowed, time, owned, old_time);
+        // At least the string of every line is saved.
+        assert!(borrowed + lines <= owned, "day{}: whole {} vs line by line {}", day, borrowed, owned);
+    }
+
+    // Day 1 keeps its lines as slices of the input: a handful of
+    // allocations for the list of lines instead of one per line.
+    let input = gen::input(2023, 1, 0, &size).unwrap();
+    let (_, owned, _) = measure(|| parse_line_by_line(1, &input));
+    let (_, borrowed, _) = measure(|| parse_whole(1, &input));
+    assert!(borrowed * 100 < owned, "borrowed {} vs owned {}", borrowed, owned);
+}
+
+#[test]
+fn test_measure() {
+    let (_, usage) = memory::measure(|| {
+        let big = vec![0u8; 1 << 20];
+        drop(big);
+        let (small, inner) = memory::measure(|| vec![0u8; 1000]);
+        assert_eq!(inner, Some(Usage { allocations: 1, bytes: 1000, peak: 1000 }));
+        small
+    });
+    let usage = usage.unwrap();
+    assert_eq!((usage.allocations, usage.bytes), (2, (1 << 20) + 1000));
+    assert_eq!(usage.peak, 1 << 20, "the freed megabyte is the peak");
+
+    // Runs carry the heap use of each step.
+    let run = DAYS[0].run("a1b2c\n", 2).unwrap();
+    assert!(run.parse_memory.is_some_and(|usage| usage.allocations > 0));
+    assert!(run.part_memory.iter().all(Option::is_some));
+}
@@ -0,0 +1,59 @@
+use rust_advent::examples;
+use rust_advent::inputs;
+use rust_advent::solver::{Day, Solver};
+use rust_advent::y2023::day1::Day1;
+use rust_advent::y2023::day4::Day4;
+use rust_advent::y2023::day5::{Day5, Range};
+use rust_advent::YEARS;
+use std::fs;
+
+/// Every solver of every year.
+fn solvers() -> impl Iterator<Item = &'static dyn Solver> {
+    YEARS.iter().flat_map(|year| year.days.iter().copied())
+}
+
+#[test]
+fn test_examples() {
+    for solver in solvers() {
+        let examples = examples::load(solver.year(), solver.day()).unwrap();
+        assert!(!examples.is_empty(), "{} day{} has no examples", solver.year(), solver.day());
+        for example in examples {
+            let run = solver.run(&example.input, 1).unwrap();
+            for check in examples::check(&run, &example.expected) {
+                assert!(check.passed(), "day{} {}: {}", solver.day(), example.name, check);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_models_are_public() {
+    let cards = Day4::parse("Card 1: 1 2 3 | 3 2 9\nCard 2: 5 | 6").unwrap();
+    assert_eq!(cards.iter().map(|card| card.matches()).collect::<Vec<_>>(), [2, 0]);
+    assert_eq!(Day4::p2(&cards).unwrap(), 3);
+
+    let almanac = Day5::parse(&examples::demo(2023, 5).unwrap()).unwrap();
+    assert_eq!(almanac.seeds, [79, 14, 55, 13]);
+    let range = Range::start_end(10, 19).unwrap();
+    assert_eq!(range.substract(&Range::start_end(12, 13).unwrap()).len(), 2);
+}
+
+#[test]
+fn test_run_read_input() {
+    let run = Day1.run(&inputs::read_from("1abc2\npqr3stu8vwx\n".as_bytes()).unwrap(), 1).unwrap();
+    assert_eq!(run.answers, [Ok(50), Ok(50)]);
+    let input = inputs::read_from(&include_bytes!("../input/2023/demo5.txt.gz")[..]).unwrap();
+    assert_eq!(Day5.run(&input, 1).unwrap().answers, [Ok(35), Ok(46)]);
+}
+
+#[test]
+fn test_models_round_trip() {
+    for solver in solvers() {
+        let (year, day) = (solver.year(), solver.day());
+        let mut inputs: Vec<String> = examples::load(year, day).unwrap().into_iter().map(|e| e.input).collect();
+        inputs.push(fs::read_to_string(inputs::dir(year).join(format!("input{}.txt", day))).unwrap());
+        for input in inputs {
+            solver.round_trip(&input).unwrap_or_else(|e| panic!("{} day{}: {}", year, day, e));
+        }
+    }
+}